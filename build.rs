@@ -0,0 +1,112 @@
+//! Generates the quest registry from the modules in `src/quests/`, so adding a
+//! quest only needs a new `<event>/questNN.rs` file. Event directories are
+//! named `y<year>` for yearly events and `story<NN>` for stories.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let quests_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/quests");
+    println!("cargo::rerun-if-changed={}", quests_dir.display());
+
+    let mut events: Vec<(String, String)> = read_dir_names(&quests_dir)
+        .into_iter()
+        .filter_map(|name| {
+            let event = if let Some(year) = name.strip_prefix('y') {
+                format!("Event::Year({})", parse_digits(year, 4)?)
+            } else {
+                format!(
+                    "Event::Story({})",
+                    parse_digits(name.strip_prefix("story")?, 2)?
+                )
+            };
+            Some((name, event))
+        })
+        .collect();
+    events.sort();
+
+    let mut registry = String::new();
+    let mut entries = Vec::new();
+    let mut module_numbers = Vec::new();
+    for (event_module, event) in events.iter() {
+        let event_name = match event_module.strip_prefix('y') {
+            Some(year) => format!("the {year} event"),
+            None => format!(
+                "story {}",
+                event_module["story".len()..].trim_start_matches('0')
+            ),
+        };
+        let event_dir = quests_dir.join(event_module);
+        let mut modules: Vec<String> = read_dir_names(&event_dir)
+            .into_iter()
+            .filter_map(|file_name| {
+                let module = file_name.strip_suffix(".rs")?;
+                parse_digits(module.strip_prefix("quest")?, 2)?;
+                Some(module.to_string())
+            })
+            .collect();
+        modules.sort();
+
+        writeln!(registry, "/// The solved quests of {event_name}.").unwrap();
+        writeln!(registry, "pub mod {event_module} {{").unwrap();
+        for module in modules.iter() {
+            let path = event_dir.join(format!("{module}.rs"));
+            let quest_number = module["quest".len()..].trim_start_matches('0');
+            module_numbers.push(quest_number.to_string());
+            writeln!(registry, "    /// Quest {quest_number} of {event_name}.").unwrap();
+            writeln!(registry, "    #[path = {:?}]", path.display().to_string()).unwrap();
+            writeln!(registry, "    pub mod {module};").unwrap();
+            entries.push(format!(
+                "({event}, &{event_module}::{module}::Quest{})",
+                &module[5..]
+            ));
+        }
+        writeln!(registry, "}}").unwrap();
+    }
+
+    writeln!(
+        registry,
+        "\n/// Every solved quest with its event, in order of event and quest number."
+    )
+    .unwrap();
+    writeln!(registry, "pub static QUESTS: &[(Event, &dyn Quest)] = &[").unwrap();
+    for entry in entries {
+        writeln!(registry, "    {entry},").unwrap();
+    }
+    writeln!(registry, "];").unwrap();
+
+    writeln!(
+        registry,
+        "\n/// The number in the module name of each quest in `QUESTS`, to check them against."
+    )
+    .unwrap();
+    writeln!(registry, "#[cfg(test)]").unwrap();
+    writeln!(
+        registry,
+        "static MODULE_NUMBERS: &[usize] = &[{}];",
+        module_numbers.join(", ")
+    )
+    .unwrap();
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out_path, registry).expect("registry should be writable");
+}
+
+fn read_dir_names(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("{} should be readable: {err}", dir.display()))
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect()
+}
+
+fn parse_digits(digits: &str, len: usize) -> Option<u32> {
+    if digits.len() == len && digits.bytes().all(|b| b.is_ascii_digit()) {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
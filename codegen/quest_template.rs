@@ -1,63 +1,81 @@
 use std::io::BufRead;
 
-use crate::common::{Config, Part};
+use crate::{
+    common::{Answer, Config},
+    quests::Quest,
+};
 
-pub fn solve(part: Part, input: impl BufRead, config: &Config) -> color_eyre::Result<String> {
-    let solution = match part {
-        Part::One => "todo",
-        Part::Two => "todo",
-        Part::Three => "todo",
-    };
-    Ok(format!("{solution}"))
+/// Solves quest {{quest_num}}.
+pub struct Quest{{quest_num_padded}};
+
+impl Quest for Quest{{quest_num_padded}} {
+    fn number(&self) -> usize {
+        {{quest_num}}
+    }
+
+    fn title(&self) -> &'static str {
+        "{{title}}"
+    }
+
+    fn part_one(&self, _input: &mut dyn BufRead, _config: &Config) -> color_eyre::Result<Answer> {
+        Ok("todo".into())
+    }
+
+    fn part_two(&self, _input: &mut dyn BufRead, _config: &Config) -> color_eyre::Result<Answer> {
+        Ok("todo".into())
+    }
+
+    fn part_three(&self, _input: &mut dyn BufRead, _config: &Config) -> color_eyre::Result<Answer> {
+        Ok("todo".into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::file_reader;
-    use std::io::Cursor;
+    use crate::{
+        common::{file_reader, Event, Part},
+        examples::assert_examples,
+    };
 
-    const CONFIG: Config = Config::test();
+    const CONFIG: Config = Config::test({{event_expr}});
 
     #[test]
     #[ignore = "todo"]
     fn solve_part_one_example() {
-        let input = Cursor::new("");
-        assert_eq!("", solve(Part::One, input, &CONFIG).unwrap());
+        assert_examples(&Quest{{quest_num_padded}}, Part::One, &CONFIG);
     }
 
     #[test]
     #[ignore = "todo"]
     fn solve_part_one() {
-        let input = file_reader("notes/q{{quest_num_padded}}p01").unwrap();
-        assert_eq!("", solve(Part::One, input, &CONFIG).unwrap());
+        let mut input = file_reader("notes/{{event}}/q{{quest_num_padded}}p01").unwrap();
+        assert_eq!("", Quest{{quest_num_padded}}.solve(Part::One, &mut input, &CONFIG).unwrap());
     }
 
     #[test]
     #[ignore = "todo"]
     fn solve_part_two_example() {
-        let input = Cursor::new("");
-        assert_eq!("", solve(Part::Two, input, &CONFIG).unwrap());
+        assert_examples(&Quest{{quest_num_padded}}, Part::Two, &CONFIG);
     }
 
     #[test]
     #[ignore = "todo"]
     fn solve_part_two() {
-        let input = file_reader("notes/q{{quest_num_padded}}p02").unwrap();
-        assert_eq!("", solve(Part::Two, input, &CONFIG).unwrap());
+        let mut input = file_reader("notes/{{event}}/q{{quest_num_padded}}p02").unwrap();
+        assert_eq!("", Quest{{quest_num_padded}}.solve(Part::Two, &mut input, &CONFIG).unwrap());
     }
 
     #[test]
     #[ignore = "todo"]
     fn solve_part_three_example() {
-        let input = Cursor::new("");
-        assert_eq!("", solve(Part::Three, input, &CONFIG).unwrap());
+        assert_examples(&Quest{{quest_num_padded}}, Part::Three, &CONFIG);
     }
 
     #[test]
     #[ignore = "todo"]
     fn solve_part_three() {
-        let input = file_reader("notes/q{{quest_num_padded}}p03").unwrap();
-        assert_eq!("", solve(Part::Three, input, &CONFIG).unwrap());
+        let mut input = file_reader("notes/{{event}}/q{{quest_num_padded}}p03").unwrap();
+        assert_eq!("", Quest{{quest_num_padded}}.solve(Part::Three, &mut input, &CONFIG).unwrap());
     }
 }
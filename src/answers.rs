@@ -0,0 +1,263 @@
+//! Checking answers against the ones known to be right or wrong.
+
+use std::{cmp::Ordering, collections::HashMap, fmt::Display, io::BufRead, path::Path};
+
+use crate::common::{file_reader, Answer, Part};
+
+/// Known answers to the puzzle notes, read from the `answers` file in the
+/// notes directory. Each line has one of these forms:
+///
+/// ```text
+/// q01p01 1328     the correct answer
+/// q01p02 != 5600  an answer that was rejected
+/// q01p02 < 6000   the answer is lower than this (a guess was too high)
+/// q01p02 > 5000   the answer is higher than this (a guess was too low)
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(usize, Part), Known>,
+}
+
+#[derive(Debug, Default)]
+struct Known {
+    answer: Option<Answer>,
+    wrong: Vec<Answer>,
+    below: Option<Answer>,
+    above: Option<Answer>,
+}
+
+/// How an answer compares with what is known about the correct answer.
+#[derive(Debug, PartialEq)]
+pub enum Verification {
+    /// The answer is the correct one.
+    Correct,
+    /// The answer is known to be wrong.
+    Wrong {
+        /// The correct answer, if it is known rather than only that this
+        /// answer is wrong.
+        expected: Option<Answer>,
+        /// Whether the answer was too high (`Greater`) or too low (`Less`),
+        /// if that is known.
+        hint: Option<Ordering>,
+    },
+    /// Nothing is known about the correct answer.
+    Unknown,
+}
+
+impl Answers {
+    /// Loads the `answers` file of the notes, or no answers if there is no
+    /// such file.
+    pub fn load(notes_dir: &Path) -> color_eyre::Result<Self> {
+        let path = notes_dir.join("answers");
+        if path.exists() {
+            Self::parse(file_reader(path)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Parses answers in the format of the `answers` file.
+    pub fn parse(input: impl BufRead) -> color_eyre::Result<Self> {
+        let mut answers: HashMap<_, Known> = HashMap::new();
+        for (line_idx, line) in input.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, answer)) = line.split_once(char::is_whitespace) else {
+                return Err(color_eyre::eyre::eyre!(
+                    "Expected 'q<quest>p<part> <answer>' on line {}",
+                    line_idx + 1
+                ));
+            };
+            let key = Part::parse_key(key).ok_or_else(|| {
+                color_eyre::eyre::eyre!("Invalid key '{key}' on line {}", line_idx + 1)
+            })?;
+
+            let known = answers.entry(key).or_default();
+            let answer = answer.trim();
+            if let Some(wrong) = answer.strip_prefix("!=") {
+                known.wrong.push(wrong.trim().parse()?);
+            } else if let Some(below) = answer.strip_prefix('<') {
+                known.below = Some(below.trim().parse()?);
+            } else if let Some(above) = answer.strip_prefix('>') {
+                known.above = Some(above.trim().parse()?);
+            } else {
+                known.answer = Some(answer.parse()?);
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    /// The correct answer to a part, if it is known.
+    pub fn get(&self, quest_number: usize, part: Part) -> Option<&Answer> {
+        self.answers.get(&(quest_number, part))?.answer.as_ref()
+    }
+
+    /// Checks an answer against everything that is known about a part.
+    pub fn verify(&self, quest_number: usize, part: Part, answer: &Answer) -> Verification {
+        let Some(known) = self.answers.get(&(quest_number, part)) else {
+            return Verification::Unknown;
+        };
+
+        if let Some(expected) = &known.answer {
+            return Verification::against(expected, answer);
+        }
+
+        if known.wrong.contains(answer) {
+            Verification::Wrong {
+                expected: None,
+                hint: None,
+            }
+        } else if known
+            .below
+            .as_ref()
+            .and_then(|below| answer.compare(below))
+            .is_some_and(Ordering::is_ge)
+        {
+            Verification::Wrong {
+                expected: None,
+                hint: Some(Ordering::Greater),
+            }
+        } else if known
+            .above
+            .as_ref()
+            .and_then(|above| answer.compare(above))
+            .is_some_and(Ordering::is_le)
+        {
+            Verification::Wrong {
+                expected: None,
+                hint: Some(Ordering::Less),
+            }
+        } else {
+            Verification::Unknown
+        }
+    }
+}
+
+impl Verification {
+    /// Checks an answer against the correct answer.
+    pub fn against(expected: &Answer, answer: &Answer) -> Self {
+        if answer == expected {
+            Self::Correct
+        } else {
+            Self::Wrong {
+                expected: Some(expected.clone()),
+                hint: answer.compare(expected),
+            }
+        }
+    }
+
+    /// Whether a wrong answer was too high or too low, if that is known.
+    pub fn hint_description(&self) -> Option<&'static str> {
+        match self {
+            Self::Wrong {
+                hint: Some(Ordering::Greater),
+                ..
+            } => Some("too high"),
+            Self::Wrong {
+                hint: Some(Ordering::Less),
+                ..
+            } => Some("too low"),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "\x1b[32mPASS\x1b[0m"),
+            Self::Wrong { expected, .. } => {
+                write!(f, "\x1b[31mFAIL\x1b[0m (")?;
+                match (expected, self.hint_description()) {
+                    (Some(expected), Some(hint)) => write!(f, "{hint}, expected {expected}")?,
+                    (Some(expected), None) => write!(f, "expected {expected}")?,
+                    (None, Some(hint)) => write!(f, "{hint}")?,
+                    (None, None) => write!(f, "known to be wrong")?,
+                }
+                write!(f, ")")
+            }
+            Self::Unknown => write!(f, "\x1b[33m????\x1b[0m (no known answer)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_skips_comments_and_blank_lines() {
+        let answers =
+            Answers::parse(Cursor::new("# comment\n\nq01p02 5626\nq10p03  hello\n")).unwrap();
+        assert_eq!(Some(&Answer::Int(5626)), answers.get(1, Part::Two));
+        assert_eq!(Some(&Answer::from("hello")), answers.get(10, Part::Three));
+        assert_eq!(None, answers.get(1, Part::One));
+    }
+
+    #[test]
+    fn parse_invalid_key() {
+        assert!(Answers::parse(Cursor::new("q01p04 12\n")).is_err());
+        assert!(Answers::parse(Cursor::new("quest1 12\n")).is_err());
+        assert!(Answers::parse(Cursor::new("q01p01\n")).is_err());
+    }
+
+    #[test]
+    fn verify() {
+        let answers = Answers::parse(Cursor::new("q01p01 1328\n")).unwrap();
+        assert_eq!(
+            Verification::Correct,
+            answers.verify(1, Part::One, &Answer::from(1328))
+        );
+        assert_eq!(
+            Verification::Wrong {
+                expected: Some(Answer::Int(1328)),
+                hint: Some(Ordering::Greater),
+            },
+            answers.verify(1, Part::One, &Answer::from(1329))
+        );
+        assert_eq!(
+            Verification::Unknown,
+            answers.verify(1, Part::Two, &Answer::from(1328))
+        );
+    }
+
+    #[test]
+    fn verify_number_built_as_text() {
+        let answers = Answers::parse(Cursor::new("q05p01 3452\n")).unwrap();
+        assert_eq!(
+            Verification::Correct,
+            answers.verify(5, Part::One, &Answer::from(String::from("3452")))
+        );
+        assert_eq!(
+            Verification::Correct,
+            answers.verify(5, Part::One, &Answer::Text("3452".to_string()))
+        );
+    }
+
+    #[test]
+    fn verify_against_wrong_guesses() {
+        let answers =
+            Answers::parse(Cursor::new("q02p01 != 40\nq02p01 < 50\nq02p01 > 30\n")).unwrap();
+        let verify = |answer: i32| {
+            answers
+                .verify(2, Part::One, &Answer::from(answer))
+                .hint_description()
+        };
+        assert!(matches!(
+            answers.verify(2, Part::One, &Answer::from(40)),
+            Verification::Wrong { hint: None, .. }
+        ));
+        assert_eq!(Some("too high"), verify(50));
+        assert_eq!(Some("too low"), verify(29));
+        assert_eq!(None, verify(35));
+        assert_eq!(
+            Verification::Unknown,
+            answers.verify(2, Part::One, &Answer::from(35))
+        );
+    }
+}
@@ -0,0 +1,418 @@
+use std::{ffi::OsString, num::NonZeroUsize, path::PathBuf, time::Duration};
+
+use clap::{
+    error::ErrorKind, parser::ValueSource, ArgAction, Args, CommandFactory, FromArgMatches, Parser,
+    Subcommand, ValueEnum,
+};
+
+use everybody_codes::{
+    common::{Event, InputSource, Part, Visualize},
+    quests,
+};
+
+/// Solutions to the Everybody Codes puzzles.
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Options for `run` when no command is given
+    #[command(flatten)]
+    pub run: RunArgs,
+
+    /// Log more while solving: `-v` for info, `-vv` for debug, `-vvv` for trace
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Log levels for individual modules, e.g. `quest03=trace,y2024=debug`
+    #[arg(long, global = true)]
+    pub log: Option<String>,
+
+    /// Directory containing the puzzle notes (inputs)
+    #[arg(long, global = true, default_value = "notes")]
+    pub notes_dir: PathBuf,
+
+    /// Show each step of quests that simulate something, when running them
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        conflicts_with_all = ["record", "export_frames"]
+    )]
+    pub visualize: Option<VisualizeMode>,
+
+    /// Milliseconds between frames of `--visualize animate` and exported GIFs
+    #[arg(long, global = true, default_value_t = 100)]
+    pub frame_delay: u64,
+
+    /// Write each step of quests that simulate something to this file
+    #[arg(long, global = true, conflicts_with = "export_frames")]
+    pub record: Option<PathBuf>,
+
+    /// Save each step of quests that simulate something as PNG images and an
+    /// animated GIF, in a directory in this one for each part, with examples
+    /// under `examples/<name>/`
+    #[arg(long, global = true)]
+    pub export_frames: Option<PathBuf>,
+
+    /// Event or story to solve, e.g. `2024` or `story1` [default: latest year]
+    #[arg(short = 'E', long, global = true, value_parser = parse_event)]
+    pub event: Option<Event>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Solve quests and print their answers (the default)
+    Run(RunArgs),
+    /// Verify solutions against the known answers
+    Check(Selection),
+    /// Time how long each solution takes
+    Bench(BenchArgs),
+    /// Write random inputs, for stress testing and benchmarking
+    Gen(GenArgs),
+    /// Create the module and notes files for a new quest
+    New {
+        /// Number of the quest to create
+        quest: usize,
+
+        /// Title of the quest
+        #[arg(short, long, default_value = "todo")]
+        title: String,
+    },
+    /// List the quests that have been solved
+    List,
+    /// Show details about a quest and its notes
+    Info {
+        /// Number of the quest to describe
+        quest: usize,
+    },
+}
+
+#[derive(Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Number of parts to solve at the same time, 0 for one per CPU
+    #[arg(short, long, default_value_t = 1)]
+    pub jobs: usize,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Check each part's examples before solving it
+    #[arg(short, long)]
+    pub examples: bool,
+
+    /// Run again whenever the notes or quest sources change
+    #[arg(short, long)]
+    pub watch: bool,
+}
+
+#[derive(Args)]
+pub struct InputArgs {
+    /// Read the input from this file instead of the notes directory, or `-`
+    /// to read it from stdin
+    #[arg(short, long, conflicts_with = "text")]
+    pub input: Option<PathBuf>,
+
+    /// Use this text as the input instead of the notes directory
+    #[arg(short, long)]
+    pub text: Option<String>,
+}
+
+impl Cli {
+    /// Parses the command line, exiting with a usage error if it is invalid.
+    pub fn parse_args() -> Self {
+        Self::try_parse_args(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+
+    /// Parses arguments like [`Parser::try_parse_from`], but rejects options
+    /// for `run` given before another command, which would otherwise be
+    /// ignored.
+    pub fn try_parse_args(
+        args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
+    ) -> Result<Self, clap::Error> {
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(args)?;
+        let cli = Self::from_arg_matches(&matches)?;
+        if cli.command.is_none() {
+            return Ok(cli);
+        }
+
+        // Global options apply to every command, so only the rest are `run`'s
+        let run_option = command.get_arguments().find(|arg| {
+            !arg.is_global_set()
+                && matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+        });
+        match run_option {
+            Some(arg) => {
+                let name = match arg.get_long() {
+                    Some(long) => format!("--{long}"),
+                    None => arg.get_id().to_string(),
+                };
+                Err(command.error(
+                    ErrorKind::ArgumentConflict,
+                    format!("'{name}' is an option of `run`, give it after the command instead"),
+                ))
+            }
+            None => Ok(cli),
+        }
+    }
+
+    /// How to show the steps of simulations.
+    pub fn visualize(&self) -> color_eyre::Result<Visualize> {
+        let delay = Duration::from_millis(self.frame_delay);
+        Ok(match (self.visualize, &self.record, &self.export_frames) {
+            (Some(VisualizeMode::Text), ..) => Visualize::Text,
+            (Some(VisualizeMode::Colour), ..) => Visualize::Colour,
+            (Some(VisualizeMode::Animate), ..) => Visualize::Animate(delay),
+            (None, Some(path), _) => Visualize::record(path)?,
+            (None, None, Some(dir)) => Visualize::Export {
+                dir: dir.clone(),
+                delay,
+            },
+            (None, None, None) => Visualize::Off,
+        })
+    }
+}
+
+impl InputArgs {
+    /// The input given on the command line, or `None` if the notes should be
+    /// used.
+    pub fn source(&self) -> color_eyre::Result<Option<InputSource>> {
+        Ok(match (&self.input, &self.text) {
+            (Some(path), _) if path.as_os_str() == "-" => Some(InputSource::stdin()?),
+            (Some(path), _) => Some(InputSource::File(path.clone())),
+            (None, Some(text)) => Some(InputSource::Text(text.clone())),
+            (None, None) => None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum VisualizeMode {
+    /// Print each step as plain text
+    Text,
+    /// Print each step in colour
+    Colour,
+    /// Redraw each step in place, in colour
+    Animate,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    Text,
+    /// A JSON array with a record for each part
+    Json,
+    /// A JSON record for each part on its own line, printed as they finish
+    Ndjson,
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Number of timed runs of each part
+    #[arg(short = 'n', long, default_value = "100")]
+    pub iterations: NonZeroUsize,
+
+    /// Number of untimed runs of each part before timing it
+    #[arg(long, default_value_t = 3)]
+    pub warmup: usize,
+
+    /// Compare the timings with a baseline saved by `--save-baseline`
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Save the timings to this file
+    #[arg(long)]
+    pub save_baseline: Option<PathBuf>,
+
+    /// Percentage change in median solve time to report as a regression
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+}
+
+#[derive(Args)]
+pub struct GenArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Size of each input, such as the number of nails or the width of a map
+    #[arg(short = 'n', long, default_value = "100")]
+    pub size: NonZeroUsize,
+
+    /// Seed for the random numbers, the same seed gives the same inputs
+    #[arg(short, long, default_value_t = 1)]
+    pub seed: u64,
+
+    /// Write the inputs to this directory, named like the notes (e.g. `q01p02`)
+    /// [default: print to stdout]
+    #[arg(short, long)]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Args, Default)]
+pub struct Selection {
+    /// Quests to solve, e.g. `3`, `1,3` or `1..4` [default: all]
+    #[arg(short, long)]
+    pub quest: Option<NumberList>,
+
+    /// Parts to solve, e.g. `2` or `2,3` [default: all]
+    #[arg(short, long, value_parser = parse_parts)]
+    pub part: Option<NumberList>,
+}
+
+impl Selection {
+    pub fn quests(&self, event: Event) -> color_eyre::Result<Vec<usize>> {
+        match &self.quest {
+            Some(numbers) => {
+                if let Some(quest) = numbers
+                    .0
+                    .iter()
+                    .find(|&&quest| quests::get(event, quest).is_none())
+                {
+                    return Err(color_eyre::eyre::eyre!(
+                        "Quest {quest} of {event} has not been solved yet"
+                    ));
+                }
+                Ok(numbers.0.clone())
+            }
+            None => Ok(quests::all(event).map(|quest| quest.number()).collect()),
+        }
+    }
+
+    pub fn parts(&self) -> Vec<Part> {
+        match &self.part {
+            Some(parts) => parts
+                .0
+                .iter()
+                .map(|&part| Part::try_from(part).unwrap())
+                .collect(),
+            None => vec![Part::One, Part::Two, Part::Three],
+        }
+    }
+}
+
+/// A list of numbers given on the command line as comma-separated numbers
+/// and inclusive ranges, e.g. `1,3..5` for 1, 3, 4 and 5.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberList(Vec<usize>);
+
+impl NumberList {
+    /// Ranges are expanded, so they can't end past this, which is well beyond
+    /// the number of quests or parts in any event.
+    const MAX_RANGE_END: usize = 1000;
+}
+
+impl std::str::FromStr for NumberList {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numbers = Vec::new();
+        for item in s.split(',') {
+            let item = item.trim();
+            if let Some((start, end)) = item.split_once("..") {
+                let end = end.strip_prefix('=').unwrap_or(end);
+                let start = parse_number(start)?;
+                let end = parse_number(end)?;
+                if start > end {
+                    return Err(format!("range `{item}` is empty"));
+                }
+                if end > Self::MAX_RANGE_END {
+                    return Err(format!(
+                        "range `{item}` is too long, it must end by {}",
+                        Self::MAX_RANGE_END
+                    ));
+                }
+                numbers.extend(start..=end);
+            } else {
+                numbers.push(parse_number(item)?);
+            }
+        }
+        numbers.sort_unstable();
+        numbers.dedup();
+        Ok(Self(numbers))
+    }
+}
+
+fn parse_number(s: &str) -> Result<usize, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("`{s}` is not a number"))
+}
+
+fn parse_event(s: &str) -> Result<Event, String> {
+    s.parse().map_err(|err: color_eyre::Report| err.to_string())
+}
+
+fn parse_parts(s: &str) -> Result<NumberList, String> {
+    let parts: NumberList = s.parse()?;
+    if let Some(part) = parts.0.iter().find(|&&part| !(1..=3).contains(&part)) {
+        return Err(format!(
+            "part {part} does not exist, must be between 1 and 3"
+        ));
+    }
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_options_before_another_command() {
+        let parse = |args: &[&str]| Cli::try_parse_args(["everybody-codes"].iter().chain(args));
+        let err = parse(&["-q", "3", "-p", "1", "check"]).err().unwrap();
+        assert_eq!(ErrorKind::ArgumentConflict, err.kind());
+        assert!(parse(&["check", "-q", "3", "-p", "1"]).is_ok());
+        assert!(parse(&["-v", "-E", "2024", "check"]).is_ok());
+        let cli = parse(&["-q", "3"]).unwrap();
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn number_list_single() {
+        assert_eq!(Ok(NumberList(vec![3])), "3".parse());
+    }
+
+    #[test]
+    fn number_list_commas_and_ranges() {
+        assert_eq!(Ok(NumberList(vec![1, 3, 4, 5])), "1,3..5".parse());
+        assert_eq!(Ok(NumberList(vec![2, 3])), "2..=3".parse());
+        assert_eq!(Ok(NumberList(vec![1, 2])), "2,1,2".parse());
+    }
+
+    #[test]
+    fn number_list_invalid() {
+        assert!("".parse::<NumberList>().is_err());
+        assert!("a".parse::<NumberList>().is_err());
+        assert!("4..1".parse::<NumberList>().is_err());
+        assert!("1..100000000000".parse::<NumberList>().is_err());
+        assert!("1..=1000".parse::<NumberList>().is_ok());
+    }
+
+    #[test]
+    fn parts_out_of_range() {
+        assert!(parse_parts("2,3").is_ok());
+        assert!(parse_parts("0").is_err());
+        assert!(parse_parts("3..4").is_err());
+    }
+
+    #[test]
+    fn cli_definition_is_valid() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+}
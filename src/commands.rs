@@ -0,0 +1,17 @@
+mod bench;
+mod check;
+mod generate;
+mod info;
+mod list;
+mod new;
+mod run;
+mod watch;
+
+pub use bench::bench;
+pub use check::check;
+pub use generate::generate;
+pub use info::info;
+pub use list::list;
+pub use new::new;
+pub use run::run;
+pub use watch::watch;
@@ -0,0 +1,291 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, Cursor},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::WrapErr;
+
+use everybody_codes::{
+    common::{file_reader, Config, Event, InputSource, Part},
+    quests,
+};
+
+use crate::cli::BenchArgs;
+
+pub fn bench(args: &BenchArgs, notes_dir: &Path, config: &Config) -> color_eyre::Result<()> {
+    let baseline = match &args.baseline {
+        Some(path) => Some(
+            parse_baseline(file_reader(path)?)
+                .wrap_err_with(|| format!("Invalid baseline '{}'", path.display()))?,
+        ),
+        None => None,
+    };
+    let custom_input = args.input.source()?;
+    let mut results = Vec::new();
+    let mut num_regressions = 0;
+
+    for quest_number in args.selection.quests(config.event)? {
+        println!("\x1b[1mQuest {quest_number}\x1b[0m");
+        for part in args.selection.parts() {
+            let source = custom_input.clone().unwrap_or_else(|| {
+                InputSource::File(part.default_input_path(notes_dir, quest_number))
+            });
+
+            let quest = quests::get(config.event, quest_number).ok_or_else(|| {
+                color_eyre::eyre::eyre!("Quest {quest_number} has not been solved yet")
+            })?;
+            let input = source.read()?;
+
+            for _ in 0..args.warmup {
+                let parsed = quest
+                    .parse(&mut Cursor::new(&input))
+                    .map_err(|err| source.annotate_error(err))?;
+                quest
+                    .solve_parsed(part, &parsed, config)
+                    .map_err(|err| source.annotate_error(err))?;
+            }
+            let iterations = args.iterations.get();
+            let mut parse_samples = Vec::with_capacity(iterations);
+            let mut samples = Vec::with_capacity(iterations);
+            for _ in 0..iterations {
+                let start = Instant::now();
+                let parsed = quest
+                    .parse(&mut Cursor::new(&input))
+                    .map_err(|err| source.annotate_error(err))?;
+                parse_samples.push(start.elapsed());
+
+                let start = Instant::now();
+                quest
+                    .solve_parsed(part, &parsed, config)
+                    .map_err(|err| source.annotate_error(err))?;
+                samples.push(start.elapsed());
+            }
+            let timings = Timings {
+                parse_median: Stats::from_samples(parse_samples).median,
+                solve: Stats::from_samples(samples),
+            };
+            let previous = baseline
+                .as_ref()
+                .and_then(|baseline| baseline.get(&(config.event, quest_number, part)));
+
+            let Timings {
+                parse_median,
+                solve,
+            } = timings;
+            print!("  Part {part}: parse median {parse_median:.2?}");
+            if let Some(previous) = previous {
+                // Parsing is often too quick to time reliably, so it is shown
+                // but not counted as a regression
+                let (change, _) =
+                    describe_change(parse_median, previous.parse_median, args.threshold);
+                print!(" ({change})");
+            }
+            print!(
+                "  solve min {:.2?}  median {:.2?}  mean {:.2?}  p95 {:.2?}",
+                solve.min, solve.median, solve.mean, solve.p95
+            );
+            if let Some(previous) = previous {
+                let (change, regressed) =
+                    describe_change(solve.median, previous.solve.median, args.threshold);
+                print!("  {change}");
+                num_regressions += usize::from(regressed);
+            }
+            println!();
+
+            results.push((config.event, quest_number, part, timings));
+        }
+    }
+
+    if let Some(path) = &args.save_baseline {
+        fs::write(path, format_baseline(&results))
+            .wrap_err_with(|| format!("Could not write '{}'", path.display()))?;
+        println!("\nSaved baseline to {}", path.display());
+    }
+
+    if num_regressions > 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "{num_regressions} part(s) were more than {}% slower than the baseline",
+            args.threshold
+        ));
+    }
+
+    Ok(())
+}
+
+/// The change from a baseline median as a percentage, coloured if it is
+/// beyond the threshold, and whether it is a regression.
+fn describe_change(median: Duration, previous: Duration, threshold: f64) -> (String, bool) {
+    let percent = (median.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0;
+    if percent > threshold {
+        (format!("\x1b[31m{percent:+.1}%\x1b[0m"), true)
+    } else if percent < -threshold {
+        (format!("\x1b[32m{percent:+.1}%\x1b[0m"), false)
+    } else {
+        (format!("{percent:+.1}%"), false)
+    }
+}
+
+/// How long a part took to parse and to solve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Timings {
+    parse_median: Duration,
+    solve: Stats,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    p95: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self {
+                min: Duration::ZERO,
+                median: Duration::ZERO,
+                mean: Duration::ZERO,
+                p95: Duration::ZERO,
+            };
+        }
+
+        samples.sort();
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+        // Nearest-rank percentile
+        let p95 = samples[(len * 95).div_ceil(100) - 1];
+
+        Self {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / len as u32,
+            p95,
+        }
+    }
+}
+
+/// Timings from an earlier run, keyed by event, quest and part, so that one
+/// file can hold the timings of several events.
+type Baseline = HashMap<(Event, usize, Part), Timings>;
+
+fn format_baseline(results: &[(Event, usize, Part, Timings)]) -> String {
+    let mut output =
+        String::from("# event key parse_median solve_min median mean p95 (nanoseconds)\n");
+    for (event, quest_number, part, timings) in results {
+        let Timings {
+            parse_median,
+            solve,
+        } = timings;
+        output += &format!(
+            "{event} {} {} {} {} {} {}\n",
+            part.key(*quest_number),
+            parse_median.as_nanos(),
+            solve.min.as_nanos(),
+            solve.median.as_nanos(),
+            solve.mean.as_nanos(),
+            solve.p95.as_nanos()
+        );
+    }
+    output
+}
+
+fn parse_baseline(input: impl BufRead) -> color_eyre::Result<Baseline> {
+    let mut baseline = HashMap::new();
+    for (line_idx, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || color_eyre::eyre::eyre!("Invalid timings on line {}", line_idx + 1);
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [event, key, parse_median, min, median, mean, p95] = fields[..] else {
+            return Err(invalid());
+        };
+        let event: Event = event.parse().map_err(|_| invalid())?;
+        let (quest_number, part) = Part::parse_key(key).ok_or_else(invalid)?;
+        let nanos = |field: &str| {
+            field
+                .parse()
+                .map(Duration::from_nanos)
+                .map_err(|_| invalid())
+        };
+        baseline.insert(
+            (event, quest_number, part),
+            Timings {
+                parse_median: nanos(parse_median)?,
+                solve: Stats {
+                    min: nanos(min)?,
+                    median: nanos(median)?,
+                    mean: nanos(mean)?,
+                    p95: nanos(p95)?,
+                },
+            },
+        );
+    }
+    Ok(baseline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples(millis(&[5, 1, 4, 2, 3]));
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(3), stats.median);
+        assert_eq!(Duration::from_millis(3), stats.mean);
+        assert_eq!(Duration::from_millis(5), stats.p95);
+    }
+
+    #[test]
+    fn stats_from_even_number_of_samples() {
+        let stats = Stats::from_samples(millis(&(1..=20).collect::<Vec<_>>()));
+        assert_eq!(Duration::from_micros(10_500), stats.median);
+        assert_eq!(Duration::from_millis(19), stats.p95);
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let timings = |parse_ms, samples: &[u64]| Timings {
+            parse_median: Duration::from_millis(parse_ms),
+            solve: Stats::from_samples(millis(samples)),
+        };
+        let results = vec![
+            (Event::Year(2024), 1, Part::One, timings(4, &[1, 2, 3])),
+            (Event::Year(2024), 3, Part::Three, timings(5, &[10, 20])),
+            (Event::Story(1), 1, Part::One, timings(6, &[7])),
+        ];
+        let baseline = parse_baseline(Cursor::new(format_baseline(&results))).unwrap();
+        assert_eq!(3, baseline.len());
+        for (event, quest_number, part, timings) in results {
+            assert_eq!(timings, baseline[&(event, quest_number, part)]);
+        }
+    }
+
+    #[test]
+    fn baseline_invalid_line() {
+        assert!(parse_baseline(Cursor::new("2024 q01p01 1 2 3 4\n")).is_err());
+        assert!(parse_baseline(Cursor::new("2024 q01p01 1 2 3 4 x\n")).is_err());
+        assert!(parse_baseline(Cursor::new("1999x q01p01 1 2 3 4 5\n")).is_err());
+        // Baselines from before the event and parse median were saved
+        assert!(parse_baseline(Cursor::new("q01p01 1 2 3 4\n")).is_err());
+    }
+}
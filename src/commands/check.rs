@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use everybody_codes::{
+    answers::{Answers, Verification},
+    common::{file_reader, Config, ParseError},
+    quests::solve,
+};
+
+use super::run::describe_error;
+use crate::cli::Selection;
+
+pub fn check(selection: &Selection, notes_dir: &Path, config: &Config) -> color_eyre::Result<()> {
+    let answers = Answers::load(notes_dir)?;
+    let mut num_correct = 0;
+    let mut num_wrong = 0;
+    let mut num_unknown = 0;
+
+    for quest_number in selection.quests(config.event)? {
+        println!("\x1b[1mQuest {quest_number}\x1b[0m");
+        for part in selection.parts() {
+            let path = part.default_input_path(notes_dir, quest_number);
+            let solution = file_reader(&path)
+                .and_then(|input| solve(quest_number, part, input, config))
+                .map_err(|err| ParseError::annotate(err, Some(&path)));
+            // A part that can't be solved fails, but the others are still
+            // checked
+            let solution = match solution {
+                Ok(solution) => solution,
+                Err(err) => {
+                    num_wrong += 1;
+                    let error = describe_error(&err).replace('\n', "\n    ");
+                    println!("  Part {part}: \x1b[31mERROR\x1b[0m {error}");
+                    continue;
+                }
+            };
+            let verification = answers.verify(quest_number, part, &solution);
+
+            match verification {
+                Verification::Correct => num_correct += 1,
+                Verification::Wrong { .. } => num_wrong += 1,
+                Verification::Unknown => num_unknown += 1,
+            }
+            match verification {
+                Verification::Wrong { .. } => {
+                    println!("  Part {part}: {verification}, got {solution}")
+                }
+                _ => println!("  Part {part}: {verification}"),
+            }
+        }
+    }
+
+    println!("\n{num_correct} passed, {num_wrong} failed, {num_unknown} unknown");
+
+    if num_wrong > 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "{num_wrong} part(s) failed, with a wrong answer or an error"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use std::fs;
+
+use color_eyre::eyre::WrapErr;
+use everybody_codes::{
+    common::{Config, Rng},
+    quests,
+};
+
+use crate::cli::GenArgs;
+
+pub fn generate(args: &GenArgs, config: &Config) -> color_eyre::Result<()> {
+    let quest_numbers = args.selection.quests(config.event)?;
+    let parts = args.selection.parts();
+    if args.out.is_none() && quest_numbers.len() * parts.len() > 1 {
+        return Err(color_eyre::eyre::eyre!(
+            "Choose a single part with --quest and --part, or write them all with --out"
+        ));
+    }
+    if let Some(dir) = &args.out {
+        fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("Could not create '{}'", dir.display()))?;
+    }
+
+    for quest_number in quest_numbers {
+        let quest = quests::get(config.event, quest_number).ok_or_else(|| {
+            color_eyre::eyre::eyre!("Quest {quest_number} has not been solved yet")
+        })?;
+        for &part in parts.iter() {
+            // Each part has its own numbers, so its input doesn't depend on
+            // which other parts are generated with it
+            let mut rng =
+                Rng::new(args.seed ^ ((quest_number as u64) << 8 | usize::from(part) as u64));
+            let input = quest
+                .generate(part, args.size.get(), &mut rng)
+                .ok_or_else(|| {
+                    color_eyre::eyre::eyre!("Quest {quest_number} has no input generator")
+                })?;
+
+            match &args.out {
+                Some(dir) => {
+                    let path = dir.join(part.key(quest_number));
+                    fs::write(&path, input)
+                        .wrap_err_with(|| format!("Could not write '{}'", path.display()))?;
+                    println!("Wrote {}", path.display());
+                }
+                None => print!("{input}"),
+            }
+        }
+    }
+
+    Ok(())
+}
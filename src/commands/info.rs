@@ -0,0 +1,40 @@
+use std::{fs, path::Path};
+
+use everybody_codes::{
+    answers::Answers,
+    common::{Event, Part},
+    quests,
+};
+
+pub fn info(event: Event, quest_number: usize, notes_dir: &Path) -> color_eyre::Result<()> {
+    let answers = Answers::load(notes_dir)?;
+
+    println!("\x1b[1mQuest {quest_number}\x1b[0m ({event})");
+    if let Some(quest) = quests::get(event, quest_number) {
+        println!("  Title: {}", quest.title());
+        println!(
+            "  Solution: src/quests/{}/quest{quest_number:02}.rs",
+            event.module_name()
+        );
+    } else {
+        println!("  Solution: not solved yet");
+    }
+
+    for part in [Part::One, Part::Two, Part::Three] {
+        let path = part.default_input_path(notes_dir, quest_number);
+        match fs::read_to_string(&path) {
+            Ok(notes) => println!(
+                "  Part {part}: {} ({} bytes, {} lines)",
+                path.display(),
+                notes.len(),
+                notes.lines().count()
+            ),
+            Err(_) => println!("  Part {part}: {} (missing)", path.display()),
+        }
+        if let Some(answer) = answers.get(quest_number, part) {
+            println!("    Answer: {answer}");
+        }
+    }
+
+    Ok(())
+}
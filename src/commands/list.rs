@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use everybody_codes::{common::Part, quests};
+
+pub fn list(notes_dir: &Path) -> color_eyre::Result<()> {
+    for event in quests::events() {
+        println!("\x1b[1m{event}\x1b[0m");
+        let notes_dir = event.notes_dir(notes_dir);
+        for quest in quests::all(event) {
+            let quest_number = quest.number();
+            let notes: Vec<String> = [Part::One, Part::Two, Part::Three]
+                .into_iter()
+                .filter(|part| part.default_input_path(&notes_dir, quest_number).exists())
+                .map(|part| format!("p{part}"))
+                .collect();
+            println!(
+                "  \x1b[1mQuest {quest_number}\x1b[0m  {}  \x1b[2mnotes: {}\x1b[0m",
+                quest.title(),
+                if notes.is_empty() {
+                    "none".to_string()
+                } else {
+                    notes.join(" ")
+                }
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::{
+    fs::{self, OpenOptions},
+    path::Path,
+};
+
+use color_eyre::eyre::WrapErr;
+
+use everybody_codes::common::{Event, Part};
+
+const TEMPLATE: &str = include_str!("../../codegen/quest_template.rs");
+
+pub fn new(
+    event: Event,
+    quest_number: usize,
+    title: &str,
+    notes_dir: &Path,
+) -> color_eyre::Result<()> {
+    let module_path = format!(
+        "src/quests/{}/quest{quest_number:02}.rs",
+        event.module_name()
+    );
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(&module_path);
+    // Check before touching anything, so a typo in the quest number doesn't
+    // leave notes behind for a quest that is already solved
+    if path.exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "File '{}' already exists",
+            path.display()
+        ));
+    }
+
+    fs::create_dir_all(notes_dir)?;
+    for part in [Part::One, Part::Two, Part::Three] {
+        touch(&part.default_input_path(notes_dir, quest_number))?;
+
+        let examples_dir = notes_dir.join("examples").join(part.key(quest_number));
+        fs::create_dir_all(&examples_dir)?;
+        touch(&examples_dir.join("1.in"))?;
+        touch(&examples_dir.join("1.answer"))?;
+    }
+
+    let content = TEMPLATE
+        .replace("{{quest_num_padded}}", &format!("{quest_number:02}"))
+        .replace("{{quest_num}}", &quest_number.to_string())
+        .replace("{{event}}", &event.to_string())
+        .replace("{{event_expr}}", &format!("Event::{event:?}"))
+        .replace(
+            "{{title}}",
+            &title.replace('\\', "\\\\").replace('"', "\\\""),
+        );
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, content)?;
+    println!("\x1b[1mCreated\x1b[0m  {module_path}");
+
+    Ok(())
+}
+
+fn touch(path: &Path) -> color_eyre::Result<()> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .wrap_err_with(|| format!("Could not create '{}'", path.display()))?;
+    println!("\x1b[1mTouched\x1b[0m  {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,449 @@
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use everybody_codes::{
+    answers::{Answers, Verification},
+    common::{Answer, Config, InputSource, ParseError, Part},
+    examples, pool,
+    quests::{self, ParseCache},
+};
+
+use crate::cli::{OutputFormat, RunArgs};
+
+pub fn run(args: &RunArgs, notes_dir: &Path, config: &Config) -> color_eyre::Result<()> {
+    let answers = Answers::load(notes_dir)?;
+    let custom_input = args.input.source()?;
+    let mut num_wrong = 0;
+    let mut num_errors = 0;
+
+    let parts: Vec<(usize, Part)> = args
+        .selection
+        .quests(config.event)?
+        .into_iter()
+        .flat_map(|quest_number| {
+            args.selection
+                .parts()
+                .into_iter()
+                .map(move |part| (quest_number, part))
+        })
+        .collect();
+
+    let jobs = match args.jobs {
+        0 => pool::available_jobs(),
+        jobs => jobs,
+    };
+
+    // Parts and examples of a quest often share the same input
+    let cache = ParseCache::default();
+    let start = Instant::now();
+    let mut current_quest = None;
+    let mut records = Vec::new();
+    pool::for_each_ordered(
+        &parts,
+        jobs,
+        |&(quest_number, part)| {
+            let examples = if args.examples {
+                run_examples(&cache, notes_dir, quest_number, part, config)
+            } else {
+                Ok(Vec::new())
+            };
+
+            let input = custom_input.clone().unwrap_or_else(|| {
+                InputSource::File(part.default_input_path(notes_dir, quest_number))
+            });
+            log::info!(
+                "Quest {quest_number} part {part}: {input} ({})",
+                input.kind()
+            );
+            let (solution, timings) = match solve(&cache, quest_number, part, &input, config) {
+                Ok((solution, timings)) => (solution, timings),
+                Err(err) => (Err(err), Timings::default()),
+            };
+            PartRun {
+                quest_number,
+                part,
+                examples,
+                input,
+                solution,
+                timings,
+            }
+        },
+        |mut run| {
+            let examples = std::mem::replace(&mut run.examples, Ok(Vec::new()))?;
+            num_wrong += examples
+                .iter()
+                .filter(|example| !matches!(example.verification, Verification::Correct))
+                .count();
+
+            // Known answers only apply to the notes, not to other inputs
+            let verification = match &run.solution {
+                Ok(solution) if custom_input.is_none() => {
+                    answers.verify(run.quest_number, run.part, solution)
+                }
+                _ => Verification::Unknown,
+            };
+            if matches!(verification, Verification::Wrong { .. }) {
+                num_wrong += 1;
+            }
+            if run.solution.is_err() {
+                num_errors += 1;
+            }
+
+            match args.format {
+                OutputFormat::Text => {
+                    if current_quest != Some(run.quest_number) {
+                        println!("\x1b[1mQuest {}\x1b[0m", run.quest_number);
+                        current_quest = Some(run.quest_number);
+                    }
+                    for example in examples.iter() {
+                        print_example_text(run.part, example);
+                    }
+                    print_text(run, &verification);
+                }
+                OutputFormat::Json | OutputFormat::Ndjson => {
+                    let record = Record::new(run, &verification, examples);
+                    if args.format == OutputFormat::Ndjson {
+                        println!("{}", serde_json::to_string(&record)?);
+                    } else {
+                        records.push(record);
+                    }
+                }
+            }
+            Ok::<_, color_eyre::Report>(())
+        },
+    )?;
+
+    match args.format {
+        OutputFormat::Text if jobs > 1 => println!(
+            "\nSolved {} part(s) on {jobs} threads in {:.2?}",
+            parts.len(),
+            start.elapsed()
+        ),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&records)?),
+        _ => {}
+    }
+
+    if num_errors > 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "{num_errors} part(s) could not be solved"
+        ));
+    }
+    if num_wrong > 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "{num_wrong} answer(s) did not match the known answers"
+        ));
+    }
+
+    Ok(())
+}
+
+struct PartRun {
+    quest_number: usize,
+    part: Part,
+    examples: color_eyre::Result<Vec<ExampleRun>>,
+    input: InputSource,
+    solution: color_eyre::Result<Answer>,
+    timings: Timings,
+}
+
+#[derive(Default)]
+struct Timings {
+    parse: Duration,
+    parse_cached: bool,
+    solve: Duration,
+}
+
+/// Parses the input, or reuses it from the cache, and then solves the part.
+/// Errors from the solution itself are returned along with the timings.
+fn solve(
+    cache: &ParseCache,
+    quest_number: usize,
+    part: Part,
+    input: &InputSource,
+    config: &Config,
+) -> color_eyre::Result<(color_eyre::Result<Answer>, Timings)> {
+    let quest = quests::get(config.event, quest_number)
+        .ok_or_else(|| color_eyre::eyre::eyre!("Quest {quest_number} has not been solved yet"))?;
+    let raw = input.read()?;
+
+    let start = Instant::now();
+    let (parsed, parse_cached) = cache
+        .parse(config.event, quest, &raw)
+        .map_err(|err| input.annotate_error(err))?;
+    let parse = start.elapsed();
+    if parse_cached {
+        log::debug!("Quest {quest_number} part {part}: reusing parsed input");
+    }
+
+    let start = Instant::now();
+    let solution = quest
+        .solve_parsed(part, &parsed, config)
+        .map_err(|err| input.annotate_error(err));
+    let timings = Timings {
+        parse,
+        parse_cached,
+        solve: start.elapsed(),
+    };
+    Ok((solution, timings))
+}
+
+struct ExampleRun {
+    name: String,
+    solution: color_eyre::Result<Answer>,
+    verification: Verification,
+}
+
+fn run_examples(
+    cache: &ParseCache,
+    notes_dir: &Path,
+    quest_number: usize,
+    part: Part,
+    config: &Config,
+) -> color_eyre::Result<Vec<ExampleRun>> {
+    Ok(examples::load(notes_dir, quest_number, part)?
+        .into_iter()
+        .map(|example| {
+            let config = Config {
+                event: config.event,
+                visualize: config.visualize.for_example(&example.name),
+            };
+            let solution = solve(cache, quest_number, part, &example.input, &config)
+                .and_then(|(solution, _)| solution);
+            let verification = match &solution {
+                Ok(solution) => Verification::against(&example.expected, solution),
+                Err(_) => Verification::Wrong {
+                    expected: Some(example.expected),
+                    hint: None,
+                },
+            };
+            ExampleRun {
+                name: example.name,
+                solution,
+                verification,
+            }
+        })
+        .collect())
+}
+
+fn print_example_text(part: Part, example: &ExampleRun) {
+    let name = &example.name;
+    let verification = &example.verification;
+    match &example.solution {
+        Ok(solution) => println!("  Part {part} example {name}: {solution} {verification}"),
+        Err(err) => println!(
+            "  Part {part} example {name}: {verification} {}",
+            describe_error(err).replace('\n', "\n    ")
+        ),
+    }
+}
+
+fn print_text(run: PartRun, verification: &Verification) {
+    let PartRun {
+        part,
+        solution,
+        timings,
+        ..
+    } = run;
+    let solution = match solution {
+        Ok(solution) => solution,
+        Err(err) => {
+            let error = describe_error(&err).replace('\n', "\n    ");
+            println!("  Part {part}: \x1b[31mERROR\x1b[0m {error}");
+            return;
+        }
+    };
+    let parse = if timings.parse_cached {
+        "cached".to_string()
+    } else {
+        format!("{:.2?}", timings.parse)
+    };
+    let elapsed = format!("\x1b[2m(parse {parse}, solve {:.2?})\x1b[0m", timings.solve);
+    match verification {
+        Verification::Unknown => println!("  Part {part}: {solution} {elapsed}"),
+        _ => println!("  Part {part}: {solution} {verification} {elapsed}"),
+    }
+}
+
+/// The result of solving one part, as written by `--format json`.
+#[derive(Serialize)]
+struct Record {
+    quest: usize,
+    part: usize,
+    input: String,
+    source: &'static str,
+    answer: Option<Answer>,
+    parse_ms: f64,
+    parse_cached: bool,
+    elapsed_ms: f64,
+    error: Option<String>,
+    verification: &'static str,
+    expected: Option<Answer>,
+    hint: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    examples: Vec<ExampleRecord>,
+}
+
+#[derive(Serialize)]
+struct ExampleRecord {
+    name: String,
+    answer: Option<Answer>,
+    error: Option<String>,
+    verification: &'static str,
+    expected: Option<Answer>,
+    hint: Option<&'static str>,
+}
+
+impl Record {
+    fn new(run: PartRun, verification: &Verification, examples: Vec<ExampleRun>) -> Self {
+        let (answer, error) = split_solution(run.solution);
+        let (verification, expected, hint) = describe_verification(verification);
+        Self {
+            quest: run.quest_number,
+            part: run.part.into(),
+            // The text itself rather than its quoted form used in messages
+            input: match &run.input {
+                InputSource::Text(text) => text.clone(),
+                input => input.to_string(),
+            },
+            source: run.input.kind(),
+            answer,
+            parse_ms: run.timings.parse.as_secs_f64() * 1000.0,
+            parse_cached: run.timings.parse_cached,
+            elapsed_ms: run.timings.solve.as_secs_f64() * 1000.0,
+            error,
+            verification,
+            expected,
+            hint,
+            examples: examples
+                .into_iter()
+                .map(|example| {
+                    let (answer, error) = split_solution(example.solution);
+                    let (verification, expected, hint) =
+                        describe_verification(&example.verification);
+                    ExampleRecord {
+                        name: example.name,
+                        answer,
+                        error,
+                        verification,
+                        expected,
+                        hint,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+fn split_solution(solution: color_eyre::Result<Answer>) -> (Option<Answer>, Option<String>) {
+    match solution {
+        Ok(answer) => (Some(answer), None),
+        Err(err) => (None, Some(describe_error(&err))),
+    }
+}
+
+/// The error message, followed by the snippet of the input with the problem
+/// underlined if it is a parse error. `{err:#}` alone leaves the snippet out.
+pub(super) fn describe_error(err: &color_eyre::Report) -> String {
+    match err.downcast_ref::<ParseError>() {
+        Some(parse_error) => format!("{err:#}\n{}", parse_error.snippet()),
+        None => format!("{err:#}"),
+    }
+}
+
+fn describe_verification(
+    verification: &Verification,
+) -> (&'static str, Option<Answer>, Option<&'static str>) {
+    let hint = verification.hint_description();
+    match verification {
+        Verification::Correct => ("correct", None, hint),
+        Verification::Wrong { expected, .. } => ("wrong", expected.clone(), hint),
+        Verification::Unknown => ("unknown", None, hint),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn record_for_wrong_answer() {
+        let run = PartRun {
+            quest_number: 3,
+            part: Part::Two,
+            examples: Ok(Vec::new()),
+            input: InputSource::File(PathBuf::from("notes/q03p02")),
+            solution: Ok(Answer::from(12)),
+            timings: Timings {
+                parse: Duration::from_micros(250),
+                parse_cached: false,
+                solve: Duration::from_micros(1500),
+            },
+        };
+        let verification = Verification::Wrong {
+            expected: Some(Answer::from(13)),
+            hint: Some(std::cmp::Ordering::Less),
+        };
+        assert_eq!(
+            r#"{"quest":3,"part":2,"input":"notes/q03p02","source":"file","answer":12,"parse_ms":0.25,"parse_cached":false,"elapsed_ms":1.5,"error":null,"verification":"wrong","expected":13,"hint":"too low"}"#,
+            serde_json::to_string(&Record::new(run, &verification, Vec::new())).unwrap()
+        );
+    }
+
+    #[test]
+    fn record_for_error() {
+        let run = PartRun {
+            quest_number: 1,
+            part: Part::One,
+            examples: Ok(Vec::new()),
+            input: InputSource::Stdin(String::new()),
+            solution: Err(color_eyre::eyre::eyre!("Expected 'WORDS:'")),
+            timings: Timings::default(),
+        };
+        let record = Record::new(run, &Verification::Unknown, Vec::new());
+        assert!(record.answer.is_none());
+        assert_eq!(Some("Expected 'WORDS:'".to_string()), record.error);
+        assert_eq!("unknown", record.verification);
+        assert_eq!("stdin", record.source);
+    }
+
+    #[test]
+    fn record_for_text_input() {
+        let run = PartRun {
+            quest_number: 1,
+            part: Part::One,
+            examples: Ok(Vec::new()),
+            input: InputSource::Text("AB\"C".to_string()),
+            solution: Ok(Answer::Int(5)),
+            timings: Timings::default(),
+        };
+        let record = Record::new(run, &Verification::Unknown, Vec::new());
+        assert_eq!("AB\"C", record.input);
+        assert_eq!("text", record.source);
+    }
+
+    #[test]
+    fn record_for_parse_error_has_snippet() {
+        let err = ParseError::new("Expected '.' or '#', found 'x'", 2, ".x.", 1..2);
+        let run = PartRun {
+            quest_number: 3,
+            part: Part::One,
+            examples: Ok(Vec::new()),
+            input: InputSource::Stdin(String::new()),
+            solution: Err(ParseError::annotate(err.into(), None)),
+            timings: Timings::default(),
+        };
+        let record = Record::new(run, &Verification::Unknown, Vec::new());
+        assert_eq!(
+            Some(
+                "Expected '.' or '#', found 'x' at line 2, column 2\n  |\n2 | .x.\n  |  ^"
+                    .to_string()
+            ),
+            record.error
+        );
+    }
+}
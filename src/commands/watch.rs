@@ -0,0 +1,159 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, SystemTime},
+};
+
+use everybody_codes::common::{Config, InputSource};
+
+use crate::cli::RunArgs;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Re-runs the selected quests whenever their notes or the quest sources
+/// change. Each run is a fresh `cargo run`, so that changes to the solutions
+/// are compiled in.
+pub fn watch(args: &RunArgs, notes_dir: &Path, config: &Config) -> color_eyre::Result<()> {
+    let mut watched = vec![
+        notes_dir.join("answers"),
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("src/quests"),
+    ];
+    // Stdin can only be read once, so the text read here is passed on to
+    // each run
+    let stdin_input = match args.input.source()? {
+        Some(InputSource::File(path)) => {
+            watched.push(path);
+            None
+        }
+        Some(InputSource::Stdin(text)) => Some(text),
+        Some(InputSource::Text(_)) | None => None,
+    };
+    for quest_number in args.selection.quests(config.event)? {
+        for part in args.selection.parts() {
+            watched.push(part.default_input_path(notes_dir, quest_number));
+            watched.push(notes_dir.join("examples").join(part.key(quest_number)));
+        }
+    }
+
+    let mut command = run_command();
+    let mut last_snapshot = snapshot(&watched);
+    let mut changed: Option<PathBuf> = None;
+    loop {
+        print!("\x1b[2J\x1b[H");
+        match &changed {
+            Some(path) => println!("\x1b[2mChanged {}\x1b[0m\n", path.display()),
+            None => println!("\x1b[2mWatching for changes...\x1b[0m\n"),
+        }
+        if let Err(err) = run(&mut command, stdin_input.as_deref()) {
+            println!("Could not run cargo: {err}");
+        }
+
+        changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let snapshot = snapshot(&watched);
+            if snapshot != last_snapshot {
+                let changed = snapshot
+                    .iter()
+                    .find(|&(path, modified)| last_snapshot.get(path) != Some(modified))
+                    .or_else(|| {
+                        last_snapshot
+                            .iter()
+                            .find(|(path, _)| !snapshot.contains_key(*path))
+                    })
+                    .map(|(path, _)| path.clone());
+                last_snapshot = snapshot;
+                break changed;
+            }
+        };
+    }
+}
+
+/// Builds the `cargo run` command that repeats this run without `--watch`,
+/// checking the examples as well.
+fn run_command() -> process::Command {
+    let mut args: Vec<String> = env::args()
+        .skip(1)
+        .filter(|arg| arg != "--watch" && arg != "-w")
+        .collect();
+    if !args.iter().any(|arg| arg == "run") {
+        args.insert(0, "run".to_string());
+    }
+    if !args.iter().any(|arg| arg == "--examples" || arg == "-e") {
+        args.push("--examples".to_string());
+    }
+
+    let mut command = process::Command::new("cargo");
+    command
+        .current_dir(env::current_dir().unwrap_or_default())
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"));
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command.arg("--").args(args);
+    command
+}
+
+/// Runs the command until it exits, giving it `input` on stdin if there is
+/// any.
+fn run(command: &mut process::Command, input: Option<&str>) -> io::Result<()> {
+    let Some(input) = input else {
+        return command.status().map(drop);
+    };
+    let mut child = command.stdin(process::Stdio::piped()).spawn()?;
+    // Dropping the pipe closes it, so the child sees the end of the input
+    let written = child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input.as_bytes());
+    child.wait()?;
+    match written {
+        // The child exited without reading it all, e.g. if it didn't compile
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        written => written,
+    }
+}
+
+/// Modification times of the given files, and of every file inside the given
+/// directories.
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut snapshot = BTreeMap::new();
+    let mut pending: Vec<PathBuf> = paths.to_vec();
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            snapshot.insert(path, modified);
+        }
+    }
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use everybody_codes::common::Part;
+
+    #[test]
+    fn snapshot_includes_files_in_directories() {
+        let notes_dir = Path::new("notes/2024");
+        let snapshot = snapshot(&[
+            Part::One.default_input_path(notes_dir, 1),
+            notes_dir.join("examples").join(Part::Two.key(2)),
+            notes_dir.join("does-not-exist"),
+        ]);
+        assert!(snapshot.contains_key(Path::new("notes/2024/q01p01")));
+        assert!(snapshot.contains_key(Path::new("notes/2024/examples/q02p02/1.in")));
+        assert!(snapshot.contains_key(Path::new("notes/2024/examples/q02p02/1.answer")));
+        assert_eq!(3, snapshot.len());
+    }
+}
@@ -1,10 +1,44 @@
+//! Types shared by the quests and the command line: parts, events, answers,
+//! inputs, grids, searches, cycles, parse errors and visualization.
+
+use color_eyre::eyre::WrapErr;
+use serde::{Serialize, Serializer};
 use std::{
+    cmp::Ordering,
+    convert::Infallible,
     fmt::Display,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{self, BufRead, BufReader, Cursor, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
 };
 
-#[derive(Copy, Clone, PartialEq)]
+mod colour;
+mod cycle;
+mod geom;
+mod grid;
+mod image;
+mod parse_error;
+mod rng;
+mod search;
+mod sections;
+mod visualize;
+
+pub use colour::{gradient, ColourSupport, Rgb};
+pub use cycle::{find_cycle, show_cycle, state_after, Cycle};
+pub use geom::{Direction4, Direction8, Point, Vector};
+pub use grid::{Grid, View};
+pub use image::{write_gif, Image};
+#[cfg(test)]
+pub use parse_error::parse_error_message;
+pub use parse_error::{parse_at, ParseError};
+pub use rng::Rng;
+pub use search::{astar, bfs, dijkstra, Route, Search};
+pub use sections::{Section, SectionedInput, Sections};
+pub use visualize::{Frame, Visualize, Visualizer};
+
+/// One of the three parts of a quest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Part {
     One,
     Two,
@@ -12,9 +46,22 @@ pub enum Part {
 }
 
 impl Part {
-    pub fn default_input_path(self, quest_num: usize) -> String {
+    /// Path of the notes for this part, e.g. `<notes_dir>/q01p02`.
+    pub fn default_input_path(self, notes_dir: &Path, quest_num: usize) -> PathBuf {
+        notes_dir.join(self.key(quest_num))
+    }
+
+    /// Short name identifying this part of a quest, e.g. `q01p02`.
+    pub fn key(self, quest_num: usize) -> String {
         let part_num: usize = self.into();
-        format!("notes/q{:02}p{:02}", quest_num, part_num)
+        format!("q{:02}p{:02}", quest_num, part_num)
+    }
+
+    /// Parses a name produced by [`Part::key`] back into a quest number and part.
+    pub fn parse_key(key: &str) -> Option<(usize, Self)> {
+        let (quest_num, part_num) = key.strip_prefix('q')?.split_once('p')?;
+        let part_num: usize = part_num.parse().ok()?;
+        Some((quest_num.parse().ok()?, part_num.try_into().ok()?))
     }
 }
 
@@ -50,32 +97,370 @@ impl TryFrom<usize> for Part {
     }
 }
 
+/// A set of quests: either one of the yearly events, or a story.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Event {
+    /// The yearly event of the given year.
+    Year(u16),
+    /// The story with the given number.
+    Story(u8),
+}
+
+impl Event {
+    /// Directory within the notes directory that holds this event's notes.
+    pub fn notes_dir(self, notes_dir: &Path) -> PathBuf {
+        notes_dir.join(self.to_string())
+    }
+
+    /// Name of the module in `src/quests/` holding this event's quests.
+    pub fn module_name(self) -> String {
+        match self {
+            Self::Year(year) => format!("y{year}"),
+            Self::Story(story) => format!("story{story:02}"),
+        }
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Year(year) => write!(f, "{year}"),
+            Self::Story(story) => write!(f, "story{story:02}"),
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            color_eyre::eyre::eyre!(
+                "Event must be a year such as '2024' or a story such as 'story1'"
+            )
+        };
+        if let Some(story) = s.strip_prefix("story").or_else(|| s.strip_prefix('s')) {
+            Ok(Self::Story(story.parse().map_err(|_| invalid())?))
+        } else {
+            Ok(Self::Year(s.parse().map_err(|_| invalid())?))
+        }
+    }
+}
+
+/// Settings that apply to every quest being solved.
 pub struct Config {
-    pub verbose: bool,
+    /// The event whose quests are being solved.
+    pub event: Event,
+    /// How solvers that simulate something should show each step.
+    pub visualize: Visualize,
 }
 
 impl Config {
+    /// Solves the quests of the event without visualizing anything.
+    pub const fn new(event: Event) -> Self {
+        Self {
+            event,
+            visualize: Visualize::Off,
+        }
+    }
+
     #[cfg(test)]
-    pub const fn test() -> Self {
-        Self { verbose: true }
+    pub const fn test(event: Event) -> Self {
+        Self::new(event)
     }
 }
 
-pub fn file_reader(path: &str) -> color_eyre::Result<impl BufRead> {
-    Ok(BufReader::new(File::open(path)?))
+/// The answer to one part of a quest. Integers that fit in an `i64` are
+/// always stored as [`Answer::Int`], so numbers compare equal regardless of
+/// which type the solver computed them with, or whether it built them as text.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Int(i64),
+    /// A number too large for an [`Answer::Int`].
+    BigInt(u128),
+    Text(String),
+}
+
+impl Answer {
+    /// Compares two numeric answers, or returns `None` if either is text.
+    pub fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => Some(a.cmp(b)),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(a.cmp(b)),
+            (Self::Int(a), Self::BigInt(b)) => Some(match u128::try_from(*a) {
+                Ok(a) => a.cmp(b),
+                Err(_) => Ordering::Less,
+            }),
+            (Self::BigInt(_), Self::Int(_)) => other.compare(self).map(Ordering::reverse),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            // A number built directly as `Text` still equals the same number
+            (Self::Text(_), _) | (_, Self::Text(_)) => self.to_string() == other.to_string(),
+            _ => self.compare(other) == Some(Ordering::Equal),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        *self == other.parse::<Answer>().unwrap()
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        other == self
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Int(n) => write!(f, "{n}"),
+            Self::BigInt(n) => write!(f, "{n}"),
+            Self::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(n) = s.parse() {
+            Self::Int(n)
+        } else if let Ok(n) = s.parse() {
+            Self::BigInt(n)
+        } else {
+            Self::Text(s.to_string())
+        })
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Int(n) => serializer.serialize_i64(*n),
+            Self::BigInt(n) => serializer.serialize_u128(*n),
+            Self::Text(text) => serializer.serialize_str(text),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Self::Int(n),
+                        Err(_) => Self::BigInt(n as u128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(i32, u32, i64, u64, usize, u128);
+
+/// Text that is a number becomes a number, like when parsing.
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        match text.parse() {
+            Ok(Self::Text(_)) => Self::Text(text),
+            Ok(answer) => answer,
+        }
+    }
+}
+
+/// Text that is a number becomes a number, like when parsing.
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        match text.parse() {
+            Ok(answer) => answer,
+        }
+    }
+}
+
+/// Where the input for a quest part comes from.
+#[derive(Clone, Debug)]
+pub enum InputSource {
+    /// A file, which is read each time the input is used.
+    File(PathBuf),
+    /// Input that was read from stdin up front, so it can be reused by each
+    /// part.
+    Stdin(String),
+    /// Input given as text on the command line.
+    Text(String),
+}
+
+impl InputSource {
+    /// Reads all of stdin, so that it can be used more than once.
+    pub fn stdin() -> color_eyre::Result<Self> {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .wrap_err("Could not read stdin")?;
+        Ok(Self::Stdin(input))
+    }
+
+    /// Opens the input for reading.
+    pub fn reader(&self) -> color_eyre::Result<Box<dyn BufRead + '_>> {
+        Ok(match self {
+            Self::File(path) => Box::new(file_reader(path)?),
+            Self::Stdin(input) | Self::Text(input) => Box::new(Cursor::new(input.as_bytes())),
+        })
+    }
+
+    /// Reads the whole input into memory.
+    pub fn read(&self) -> color_eyre::Result<Vec<u8>> {
+        let mut input = Vec::new();
+        self.reader()?
+            .read_to_end(&mut input)
+            .wrap_err_with(|| format!("Could not read {self}"))?;
+        Ok(input)
+    }
+
+    /// Adds a snippet of this input to the report if it is a [`ParseError`].
+    pub fn annotate_error(&self, report: color_eyre::Report) -> color_eyre::Report {
+        match self {
+            Self::File(path) => ParseError::annotate(report, Some(path)),
+            Self::Stdin(_) | Self::Text(_) => ParseError::annotate(report, None),
+        }
+    }
+
+    /// Short name for the kind of input: `file`, `stdin` or `text`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::File(_) => "file",
+            Self::Stdin(_) => "stdin",
+            Self::Text(_) => "text",
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin(_) => write!(f, "<stdin>"),
+            Self::Text(text) => write!(f, "{text:?}"),
+        }
+    }
+}
+
+/// Opens a file for buffered reading, with its path in any error.
+pub fn file_reader(path: impl AsRef<Path>) -> color_eyre::Result<impl BufRead> {
+    let path = path.as_ref();
+    let file = File::open(path).wrap_err_with(|| format!("Could not open '{}'", path.display()))?;
+    Ok(BufReader::new(file))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Part;
+    use super::{Answer, Event, InputSource, Part};
+    use std::{cmp::Ordering, io::Read, path::Path};
 
     #[test]
     fn default_input_path_with_leading_zeroes() {
-        assert_eq!("notes/q06p02", Part::Two.default_input_path(6));
+        assert_eq!(
+            Path::new("notes/q06p02"),
+            Part::Two.default_input_path(Path::new("notes"), 6)
+        );
+    }
+
+    #[test]
+    fn event_notes_dir() {
+        assert_eq!(
+            Path::new("notes/2024"),
+            Event::Year(2024).notes_dir(Path::new("notes"))
+        );
+        assert_eq!(
+            Path::new("notes/story01"),
+            Event::Story(1).notes_dir(Path::new("notes"))
+        );
+    }
+
+    #[test]
+    fn parse_event() {
+        assert_eq!(Event::Year(2024), "2024".parse().unwrap());
+        assert_eq!(Event::Story(2), "story2".parse().unwrap());
+        assert_eq!(Event::Story(1), "s01".parse().unwrap());
+        assert!("winter".parse::<Event>().is_err());
+    }
+
+    #[test]
+    fn parse_key() {
+        assert_eq!(Some((6, Part::Two)), Part::parse_key(&Part::Two.key(6)));
+        assert_eq!(Some((10, Part::Three)), Part::parse_key("q10p3"));
+        assert_eq!(None, Part::parse_key("q10p4"));
+        assert_eq!(None, Part::parse_key("10p1"));
     }
 
     #[test]
     fn default_input_path_without_leading_zeroes() {
-        assert_eq!("notes/q10p03", Part::Three.default_input_path(10));
+        assert_eq!(
+            Path::new("notes/q10p03"),
+            Part::Three.default_input_path(Path::new("notes"), 10)
+        );
+    }
+
+    #[test]
+    fn answer_from_int_is_normalized() {
+        assert!(matches!(Answer::from(42usize), Answer::Int(42)));
+        assert!(matches!(Answer::from(u128::MAX), Answer::BigInt(u128::MAX)));
+        assert_eq!(Answer::Int(7), Answer::BigInt(7));
+    }
+
+    #[test]
+    fn parse_answer() {
+        assert!(matches!("-12".parse(), Ok(Answer::Int(-12))));
+        assert!(matches!(
+            "340282366920938463463374607431768211455".parse(),
+            Ok(Answer::BigInt(u128::MAX))
+        ));
+        assert!(matches!("ABBAC".parse(), Ok(Answer::Text(text)) if text == "ABBAC"));
+        assert_eq!("1328", Answer::from(1328));
+    }
+
+    #[test]
+    fn compare_answers() {
+        assert_eq!(
+            Some(Ordering::Less),
+            Answer::Int(-1).compare(&Answer::BigInt(u128::MAX))
+        );
+        assert_eq!(
+            Some(Ordering::Greater),
+            Answer::BigInt(u128::MAX).compare(&Answer::Int(5))
+        );
+        assert_eq!(None, Answer::Int(5).compare(&Answer::from("5 apples")));
+    }
+
+    #[test]
+    fn numbers_built_as_text_equal_numbers() {
+        assert!(matches!(
+            Answer::from(String::from("3452")),
+            Answer::Int(3452)
+        ));
+        assert!(matches!(Answer::from("-7"), Answer::Int(-7)));
+        assert_eq!(Answer::Int(3452), Answer::Text("3452".to_string()));
+        assert_eq!(Answer::Text("3452".to_string()), "3452");
+        assert_ne!(Answer::Int(3452), Answer::Text("03452".to_string()));
+    }
+
+    #[test]
+    fn text_input_source() {
+        let source = InputSource::Text("ABBAC".to_string());
+        let mut input = String::new();
+        source.reader().unwrap().read_to_string(&mut input).unwrap();
+        assert_eq!("ABBAC", input);
+        assert_eq!("\"ABBAC\"", source.to_string());
     }
 }
@@ -0,0 +1,95 @@
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// Stops of the gradient used by [`gradient`], from dark purple through teal
+/// to yellow (the "viridis" colour map).
+const GRADIENT: [Rgb; 5] = [
+    [68, 1, 84],
+    [59, 82, 139],
+    [33, 145, 140],
+    [94, 201, 98],
+    [253, 231, 37],
+];
+
+/// The colour `t` of the way along a gradient whose brightness increases
+/// evenly, where `t` is between 0 and 1.
+pub fn gradient(t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0) * (GRADIENT.len() - 1) as f64;
+    let idx = (t.floor() as usize).min(GRADIENT.len() - 2);
+    let (from, to) = (GRADIENT[idx], GRADIENT[idx + 1]);
+    let frac = t - idx as f64;
+    std::array::from_fn(|channel| {
+        let (from, to) = (from[channel] as f64, to[channel] as f64);
+        (from + (to - from) * frac).round() as u8
+    })
+}
+
+/// The colours that a terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColourSupport {
+    /// The 256 colour xterm palette.
+    Ansi256,
+    /// Any 24-bit colour.
+    TrueColour,
+}
+
+impl ColourSupport {
+    /// Detects true colour support from `COLORTERM`, which most terminals that
+    /// support it set.
+    pub fn detect() -> Self {
+        match std::env::var("COLORTERM").as_deref() {
+            Ok("truecolor" | "24bit") => Self::TrueColour,
+            _ => Self::Ansi256,
+        }
+    }
+
+    /// The escape code that sets the background to the colour, or the closest
+    /// one available.
+    pub fn background(self, [r, g, b]: Rgb) -> String {
+        match self {
+            Self::TrueColour => format!("\x1b[48;2;{r};{g};{b}m"),
+            Self::Ansi256 => {
+                // The palette's 6x6x6 colour cube starts at 16
+                let level = |channel: u8| (channel as u16 * 5 + 127) / 255;
+                format!(
+                    "\x1b[48;5;{}m",
+                    16 + 36 * level(r) + 6 * level(g) + level(b)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_ends_and_middle() {
+        assert_eq!(GRADIENT[0], gradient(0.0));
+        assert_eq!(GRADIENT[4], gradient(1.0));
+        assert_eq!(GRADIENT[2], gradient(0.5));
+        assert_eq!(GRADIENT[4], gradient(7.0));
+        assert_eq!([64, 42, 112], gradient(0.125));
+    }
+
+    #[test]
+    fn background_codes() {
+        assert_eq!(
+            "\x1b[48;2;1;2;3m",
+            ColourSupport::TrueColour.background([1, 2, 3])
+        );
+        assert_eq!(
+            "\x1b[48;5;16m",
+            ColourSupport::Ansi256.background([0, 0, 0])
+        );
+        assert_eq!(
+            "\x1b[48;5;231m",
+            ColourSupport::Ansi256.background([255; 3])
+        );
+        assert_eq!(
+            "\x1b[48;5;196m",
+            ColourSupport::Ansi256.background([250, 10, 0])
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use super::{Frame, Visualizer};
+
+/// Where the states of a simulation start repeating. Every simulation whose
+/// states come from a finite set ends up in a cycle, if only of one state
+/// that never changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step whose state repeats later.
+    pub start: usize,
+    /// Number of steps until a state repeats, which is at least 1.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The step that has the same state as step `n`, which is before the end
+    /// of the first time around the cycle.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Finds the cycle in the states that come from applying `step` over and over
+/// to `initial`, using Brent's algorithm. This only keeps two states at a
+/// time, so it works for large states, but never returns if the states never
+/// repeat.
+pub fn find_cycle<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let period = find_period(initial, &mut step, None)
+        .unwrap_or_else(|_| unreachable!("only stops early when given a step"));
+    cycle_with_period(initial, &mut step, period)
+}
+
+/// The state after `n` steps, found by skipping whole times around the cycle
+/// rather than taking every step, so `n` can be huge. If step `n` comes before
+/// the cycle has been found, its state is returned straight away, so states
+/// that never repeat are fine for `n` small enough to step through.
+pub fn state_after<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let period = match find_period(&initial, &mut step, Some(n)) {
+        Ok(period) => period,
+        Err(state) => return state,
+    };
+    let cycle = cycle_with_period(&initial, &mut step, period);
+    let mut state = initial;
+    for _ in 0..cycle.equivalent_step(n) {
+        state = step(&state);
+    }
+    state
+}
+
+/// Finds the period by moving the hare away from the tortoise, and moving the
+/// tortoise up to the hare each time the distance doubles, until the hare
+/// catches up with the tortoise instead. Stops early with the hare's state if
+/// it reaches step `stop_at` first.
+fn find_period<S: Clone + Eq>(
+    initial: &S,
+    step: &mut impl FnMut(&S) -> S,
+    stop_at: Option<usize>,
+) -> Result<usize, S> {
+    if stop_at == Some(0) {
+        return Err(initial.clone());
+    }
+    let mut power = 1;
+    let mut period = 1;
+    let mut steps = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if stop_at == Some(steps) {
+            return Err(hare);
+        }
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        steps += 1;
+        period += 1;
+    }
+    Ok(period)
+}
+
+/// Finds where the cycle starts, given its period.
+fn cycle_with_period<S: Clone + Eq>(
+    initial: &S,
+    step: &mut impl FnMut(&S) -> S,
+    period: usize,
+) -> Cycle {
+    // With the hare a period ahead, they first meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    log::debug!("Found a cycle of {period} step(s) starting at step {start}");
+    Cycle { start, period }
+}
+
+/// Shows the states of one time around the cycle, from the state at its start
+/// to the state that repeats it.
+pub fn show_cycle<S: Frame>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    cycle: Cycle,
+    visualizer: &mut dyn Visualizer,
+) -> color_eyre::Result<()> {
+    let Cycle { start, period } = cycle;
+    let mut state = initial;
+    for _ in 0..start {
+        state = step(&state);
+    }
+    visualizer.frame(&format!("step {start}, start of cycle"), &state)?;
+    for offset in 1..=period {
+        state = step(&state);
+        let label = if offset == period {
+            format!("step {}, same as step {start}", start + period)
+        } else {
+            format!("step {}, {offset} of {period} in cycle", start + offset)
+        };
+        visualizer.frame(&label, &state)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::common::visualize::TextVisualizer;
+
+    /// Finds the cycle by remembering every state.
+    fn find_cycle_slowly(initial: u64, step: impl Fn(&u64) -> u64) -> Cycle {
+        let mut seen = HashMap::new();
+        let mut state = initial;
+        for n in 0.. {
+            if let Some(start) = seen.insert(state, n) {
+                return Cycle {
+                    start,
+                    period: n - start,
+                };
+            }
+            state = step(&state);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn matches_remembering_every_state() {
+        for modulus in 1..60 {
+            for initial in 0..modulus {
+                let step = |&x: &u64| (x * x + 1) % modulus;
+                assert_eq!(
+                    find_cycle_slowly(initial, step),
+                    find_cycle(&initial, step),
+                    "x^2 + 1 mod {modulus} from {initial}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_point() {
+        let cycle = find_cycle(&10, |&x: &u32| x.saturating_sub(3));
+        assert_eq!(
+            Cycle {
+                start: 4,
+                period: 1
+            },
+            cycle
+        );
+        assert_eq!(
+            0,
+            state_after(10, |&x: &u32| x.saturating_sub(3), 1_000_000)
+        );
+    }
+
+    #[test]
+    fn extrapolate_to_step_n() {
+        // 3 steps before a cycle of 5: 0 1 2 3 4 5 6 7 3 4 ...
+        let step = |&x: &u32| if x == 7 { 3 } else { x + 1 };
+        let cycle = find_cycle(&0, step);
+        assert_eq!(
+            Cycle {
+                start: 3,
+                period: 5
+            },
+            cycle
+        );
+        assert_eq!(2, cycle.equivalent_step(2));
+        assert_eq!(5, cycle.equivalent_step(1_000_000_000));
+        for n in 0..30 {
+            let mut state = 0;
+            for _ in 0..n {
+                state = step(&state);
+            }
+            assert_eq!(state, state_after(0, step, n));
+        }
+    }
+
+    #[test]
+    fn states_that_never_repeat() {
+        for n in 0..100 {
+            assert_eq!(n, state_after(0, |&x: &u64| x + 1, n as usize));
+        }
+        let mut steps = 0;
+        let state = state_after(
+            0,
+            |&x: &u64| {
+                steps += 1;
+                x + 3
+            },
+            1000,
+        );
+        assert_eq!(3000, state);
+        assert_eq!(1000, steps);
+    }
+
+    struct Counter(u32);
+
+    impl Frame for Counter {
+        fn text(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn shows_one_time_around() {
+        let step = |x: &Counter| Counter(if x.0 == 3 { 1 } else { x.0 + 1 });
+        let mut output = Vec::new();
+        let cycle = Cycle {
+            start: 1,
+            period: 3,
+        };
+        show_cycle(
+            Counter(0),
+            step,
+            cycle,
+            &mut TextVisualizer::new(&mut output),
+        )
+        .unwrap();
+        let lines: Vec<String> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            vec![
+                "--- frame 0 (step 1, start of cycle) ---",
+                "1",
+                "--- frame 1 (step 2, 1 of 3 in cycle) ---",
+                "2",
+                "--- frame 2 (step 3, 2 of 3 in cycle) ---",
+                "3",
+                "--- frame 3 (step 4, same as step 1) ---",
+                "1",
+            ],
+            lines
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    fmt::Display,
+    num::TryFromIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A position on a plane, such as a cell of a [`Grid`](super::Grid). `x`
+/// increases to the right and `y` increases downwards, like in the notes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The difference between two points, such as a step in some direction.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Number of steps to `other` moving only horizontally and vertically.
+    pub fn manhattan(self, other: Self) -> u64 {
+        (other - self).manhattan_len()
+    }
+
+    /// Number of steps to `other` moving diagonally as well.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        (other - self).chebyshev_len()
+    }
+}
+
+impl Vector {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Turned a quarter turn clockwise, as seen with `y` increasing downwards.
+    pub const fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Turned a quarter turn anticlockwise.
+    pub const fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn manhattan_len(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev_len(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Self;
+
+    fn add(self, v: Vector) -> Self {
+        Self::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Self;
+
+    fn sub(self, v: Vector) -> Self {
+        Self::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Self) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Converts column and row indices to a point, failing if they are too large.
+impl TryFrom<(usize, usize)> for Point {
+    type Error = TryFromIntError;
+
+    fn try_from((x, y): (usize, usize)) -> Result<Self, Self::Error> {
+        Ok(Self::new(x.try_into()?, y.try_into()?))
+    }
+}
+
+/// Converts a point to column and row indices, failing if it has a negative
+/// coordinate rather than wrapping around to a huge index.
+impl TryFrom<Point> for (usize, usize) {
+    type Error = TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok((point.x.try_into()?, point.y.try_into()?))
+    }
+}
+
+/// One of the four directions along the axes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// A step of one in this direction.
+    pub const fn vector(self) -> Vector {
+        match self {
+            Self::Up => Vector::new(0, -1),
+            Self::Right => Vector::new(1, 0),
+            Self::Down => Vector::new(0, 1),
+            Self::Left => Vector::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the four directions along the axes or the four diagonals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// A step of one in this direction, which is diagonal for the diagonals.
+    pub const fn vector(self) -> Vector {
+        match self {
+            Self::Up => Vector::new(0, -1),
+            Self::UpRight => Vector::new(1, -1),
+            Self::Right => Vector::new(1, 0),
+            Self::DownRight => Vector::new(1, 1),
+            Self::Down => Vector::new(0, 1),
+            Self::DownLeft => Vector::new(-1, 1),
+            Self::Left => Vector::new(-1, 0),
+            Self::UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// An eighth of a turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction4> for Vector {
+    fn from(direction: Direction4) -> Self {
+        direction.vector()
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Self {
+        direction.vector()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let p = Point::new(2, 3);
+        let v = Vector::new(-1, 4);
+        assert_eq!(Point::new(1, 7), p + v);
+        assert_eq!(Point::new(3, -1), p - v);
+        assert_eq!(v, (p + v) - p);
+        assert_eq!(Vector::new(-3, 12), v * 3);
+        assert_eq!(Vector::new(1, -4), -v);
+        let mut q = p;
+        q += v;
+        q -= v * 2;
+        assert_eq!(p - v, q);
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(-2, 1), Point::new(3, -1));
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(5, a.chebyshev(b));
+        assert_eq!(0, a.manhattan(a));
+    }
+
+    #[test]
+    fn rotation_matches_turning() {
+        for direction in Direction4::ALL {
+            assert_eq!(
+                direction.turn_right().vector(),
+                direction.vector().rotate_right()
+            );
+            assert_eq!(
+                direction.turn_left().vector(),
+                direction.vector().rotate_left()
+            );
+            assert_eq!(direction.reverse().vector(), -direction.vector());
+            assert_eq!(
+                direction.vector(),
+                Direction8::from(direction).vector(),
+                "{direction:?}"
+            );
+        }
+        assert_eq!(Direction4::Right, Direction4::Up.turn_right());
+        assert_eq!(Direction4::Left, Direction4::Up.turn_left());
+        assert_eq!(Direction8::UpLeft, Direction8::Up.turn_left());
+        assert_eq!(Direction8::DownLeft, Direction8::UpRight.reverse());
+    }
+
+    #[test]
+    fn checked_conversions() {
+        assert_eq!(Ok((2, 5)), <(usize, usize)>::try_from(Point::new(2, 5)));
+        assert!(<(usize, usize)>::try_from(Point::new(-1, 5)).is_err());
+        assert_eq!(Ok(Point::new(2, 5)), Point::try_from((2, 5)));
+        assert!(Point::try_from((usize::MAX, 0)).is_err());
+    }
+}
@@ -0,0 +1,415 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut, Range},
+};
+
+use super::{Direction4, Direction8, ParseError, Point};
+
+/// A rectangular grid of cells, stored row by row, with the cell at the
+/// origin in the top left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid from its rows, or `None` if they are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a grid with a cell for each character of each line, ignoring
+    /// trailing whitespace and blank lines at the end. `cell` describes what
+    /// was expected if a character is not a valid cell.
+    pub fn parse(text: &str, cell: impl Fn(char) -> Result<T, String>) -> Result<Self, ParseError> {
+        Self::parse_lines(
+            text.lines().enumerate().map(|(idx, line)| (idx + 1, line)),
+            cell,
+        )
+    }
+
+    /// Like [`Grid::parse`], for lines that don't start at the first line of
+    /// the input, given with their line numbers.
+    pub fn parse_lines<'a>(
+        lines: impl IntoIterator<Item = (usize, &'a str)>,
+        cell: impl Fn(char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let mut lines: Vec<(usize, &str)> = lines.into_iter().collect();
+        while lines.last().is_some_and(|(_, line)| line.trim().is_empty()) {
+            lines.pop();
+        }
+
+        let mut grid = Self {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        };
+        for (line_num, line) in lines {
+            let line = line.trim_ascii_end();
+            let len = grid.cells.len();
+            for (idx, c) in line.char_indices() {
+                let parsed = cell(c).map_err(|message| {
+                    ParseError::new(message, line_num, line, idx..idx + c.len_utf8())
+                })?;
+                grid.cells.push(parsed);
+            }
+
+            let row_len = grid.cells.len() - len;
+            if grid.height == 0 {
+                grid.width = row_len;
+            } else if row_len != grid.width {
+                let width = grid.width;
+                let start = line
+                    .char_indices()
+                    .nth(width)
+                    .map_or(line.len(), |(idx, _)| idx);
+                return Err(ParseError::new(
+                    format!("Expected {width} cells like the first row, found {row_len}"),
+                    line_num,
+                    line,
+                    start..line.len(),
+                ));
+            }
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Index into `cells` of the cell at `pos`, if it is in the grid. Points
+    /// with negative coordinates are outside rather than wrapping around.
+    fn cell_index(&self, pos: Point) -> Option<usize> {
+        let (x, y): (usize, usize) = pos.try_into().ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.cell_index(pos).is_some()
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.cell_index(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.cell_index(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    /// The position wrapped around the edges of the grid, as if it repeated
+    /// forever in every direction. The grid must not be empty.
+    pub fn wrap(&self, pos: Point) -> Point {
+        let size = point(self.width, self.height);
+        Point::new(pos.x.rem_euclid(size.x), pos.y.rem_euclid(size.y))
+    }
+
+    /// The cell at the position wrapped around the edges of the grid.
+    pub fn get_wrapping(&self, pos: Point) -> &T {
+        &self[self.wrap(pos)]
+    }
+
+    /// The neighbours that share an edge with `pos`, clockwise from the one
+    /// above, leaving out any outside the grid.
+    pub fn neighbours4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction4::ALL
+            .into_iter()
+            .map(move |direction| pos + direction.vector())
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// All eight neighbours of `pos`, clockwise from the one above, leaving
+    /// out any outside the grid.
+    pub fn neighbours8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .map(move |direction| pos + direction.vector())
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Chunks of a zero width grid would panic
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Each position in the grid with its cell, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| (point(idx % width, idx / width), cell))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The smallest ranges of columns and rows that contain every cell for
+    /// which `keep` is true, or `None` if there are no such cells.
+    pub fn bounding_box(&self, keep: impl Fn(&T) -> bool) -> Option<(Range<usize>, Range<usize>)> {
+        let width = self.width.max(1);
+        let mut positions = (self.cells.iter().enumerate())
+            .filter(|(_, cell)| keep(cell))
+            .map(|(idx, _)| (idx % width, idx / width));
+        let (x, y) = positions.next()?;
+        let (mut xs, mut ys) = (x..x + 1, y..y + 1);
+        for (x, y) in positions {
+            xs = xs.start.min(x)..xs.end.max(x + 1);
+            ys = ys.start.min(y)..ys.end.max(y + 1);
+        }
+        Some((xs, ys))
+    }
+
+    /// A view of the columns `xs` of the rows `ys`, which must be in the grid.
+    pub fn view(&self, xs: Range<usize>, ys: Range<usize>) -> View<'_, T> {
+        assert!(
+            xs.start <= xs.end
+                && xs.end <= self.width
+                && ys.start <= ys.end
+                && ys.end <= self.height,
+            "view {xs:?}, {ys:?} is outside a {}x{} grid",
+            self.width,
+            self.height
+        );
+        View { grid: self, xs, ys }
+    }
+
+    /// The grid cut down to the bounding box of the cells for which `keep` is
+    /// true, or `None` if there are no such cells.
+    pub fn trimmed(&self, keep: impl Fn(&T) -> bool) -> Option<Self>
+    where
+        T: Clone,
+    {
+        let (xs, ys) = self.bounding_box(keep)?;
+        Some(self.view(xs, ys).to_grid())
+    }
+}
+
+/// The point for a column and row, which always fits for a grid in memory.
+fn point(x: usize, y: usize) -> Point {
+    Point::try_from((x, y)).expect("grid dimensions should fit in an i64")
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos} is outside a {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos} is outside a {width}x{height} grid"),
+        }
+    }
+}
+
+/// Shows each row on its own line, with nothing between the cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.view(0..self.width, 0..self.height).fmt(f)
+    }
+}
+
+/// A rectangular part of a [`Grid`], with positions relative to its top left.
+#[derive(Clone, Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    xs: Range<usize>,
+    ys: Range<usize>,
+}
+
+impl<T> View<'_, T> {
+    pub fn width(&self) -> usize {
+        self.xs.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.ys.len()
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        let (x, y): (usize, usize) = pos.try_into().ok()?;
+        (x < self.width() && y < self.height())
+            .then(|| &self.grid[point(self.xs.start + x, self.ys.start + y)])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.ys.clone().map(|y| {
+            let start = y * self.grid.width;
+            &self.grid.cells[start + self.xs.start..start + self.xs.end]
+        })
+    }
+
+    /// Copies the cells into a grid of their own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width(),
+            height: self.height(),
+            cells: self.rows().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl<T: Display> Display for View<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(text: &str) -> Grid<u32> {
+        Grid::parse(text, |c| {
+            c.to_digit(10)
+                .ok_or(format!("Expected a digit, found '{c}'"))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits("123\n456\r\n");
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(6, grid[Point::new(2, 1)]);
+        assert_eq!(None, grid.get(Point::new(3, 0)));
+        assert_eq!(None, grid.get(Point::new(-1, 0)));
+        assert_eq!("123\n456\n", grid.to_string());
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6]],
+            grid.rows().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_cell() {
+        let parse = |text| Grid::parse(text, |c| c.to_digit(10).ok_or("Expected a digit".into()));
+        let err = parse("12\n1x").unwrap_err();
+        assert_eq!("Expected a digit at line 2, column 2", err.to_string());
+        let err = parse("12\n123").unwrap_err();
+        assert_eq!(
+            "Expected 2 cells like the first row, found 3 at line 2, column 3",
+            err.to_string()
+        );
+        let lines = [(5, "12"), (6, "1")];
+        let err = Grid::<char>::parse_lines(lines, Ok).unwrap_err();
+        assert!(err.to_string().ends_with("at line 6, column 2"));
+        let err = parse("12\n\n12\n").unwrap_err();
+        assert_eq!(
+            "Expected 2 cells like the first row, found 0 at line 2, column 1",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_ignored() {
+        assert_eq!(digits("12\n34"), digits("12\n34\n\n"));
+        assert_eq!(digits("12\n34"), digits("12\r\n34\r\n  \r\n\r\n"));
+        let lines = [(3, "..#"), (4, ".##"), (5, "")];
+        let grid = Grid::<char>::parse_lines(lines, Ok).unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+    }
+
+    #[test]
+    fn from_rows_must_be_rectangular() {
+        assert_eq!(
+            Some(digits("12\n34")),
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]])
+        );
+        assert_eq!(None, Grid::from_rows(vec![vec![1, 2], vec![3]]));
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = Grid::new(3, 2, 0);
+        let points = |points: &[(i64, i64)]| -> Vec<Point> {
+            points.iter().map(|&(x, y)| Point::new(x, y)).collect()
+        };
+        assert_eq!(
+            points(&[(1, 0), (0, 1)]),
+            grid.neighbours4(Point::ORIGIN).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            points(&[(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]),
+            grid.neighbours8(Point::new(1, 1)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn wrapping_access() {
+        let grid = digits("12\n34");
+        assert_eq!(Point::new(1, 0), grid.wrap(Point::new(-1, 2)));
+        assert_eq!(&4, grid.get_wrapping(Point::new(3, -1)));
+    }
+
+    #[test]
+    fn trim_to_bounding_box() {
+        let grid = digits("0000\n0100\n0020\n0000");
+        assert_eq!(Some((1..3, 1..3)), grid.bounding_box(|&cell| cell > 0));
+        assert_eq!(Some(digits("10\n02")), grid.trimmed(|&cell| cell > 0));
+        assert_eq!(None, grid.trimmed(|&cell| cell > 2));
+    }
+
+    #[test]
+    fn views() {
+        let grid = digits("123\n456\n789");
+        let view = grid.view(1..3, 1..3);
+        assert_eq!((2, 2), (view.width(), view.height()));
+        assert_eq!(Some(&8), view.get(Point::new(0, 1)));
+        assert_eq!(None, view.get(Point::new(2, 0)));
+        assert_eq!("56\n89\n", view.to_string());
+    }
+}
@@ -0,0 +1,170 @@
+use std::{fs::File, io::BufWriter, path::Path, time::Duration};
+
+use color_eyre::eyre::WrapErr;
+
+use super::{Grid, Rgb};
+
+/// A picture made of coloured pixels, such as one frame of a visualization.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// An image with a pixel for each cell of the grid.
+    pub fn from_grid<T>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.values().map(colour).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// Draws each pixel as a square of `factor` by `factor` pixels, so that
+    /// small grids are visible.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let mut scaled = Self::new(self.width * factor, self.height * factor, [0; 3]);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.set(x, y, self.get(x / factor, y / factor));
+            }
+        }
+        scaled
+    }
+
+    /// Places the image in the top left of a larger one.
+    pub fn padded(&self, width: usize, height: usize, background: Rgb) -> Self {
+        let mut padded = Self::new(width, height, background);
+        for y in 0..self.height.min(height) {
+            for x in 0..self.width.min(width) {
+                padded.set(x, y, self.get(x, y));
+            }
+        }
+        padded
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    pub fn write_png(&self, path: &Path) -> color_eyre::Result<()> {
+        let file = File::create(path)
+            .wrap_err_with(|| format!("Could not create '{}'", path.display()))?;
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(file),
+            self.width.try_into()?,
+            self.height.try_into()?,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()?
+            .write_image_data(&self.bytes())
+            .wrap_err_with(|| format!("Could not write '{}'", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Writes the images as a looping animated GIF. Smaller images are padded to
+/// the size of the largest.
+pub fn write_gif(path: &Path, images: &[Image], delay: Duration) -> color_eyre::Result<()> {
+    let width = images.iter().map(Image::width).max().unwrap_or(0);
+    let height = images.iter().map(Image::height).max().unwrap_or(0);
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(color_eyre::eyre::eyre!(
+            "{width}x{height} is too large for a GIF"
+        ));
+    };
+
+    let file =
+        File::create(path).wrap_err_with(|| format!("Could not create '{}'", path.display()))?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), gif_width, gif_height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    // GIF delays are in hundredths of a second
+    let delay = (delay.as_millis() / 10).try_into().unwrap_or(u16::MAX);
+    for image in images {
+        let image = image.padded(width, height, [0; 3]);
+        let mut frame = gif::Frame::from_rgb(gif_width, gif_height, &image.bytes());
+        frame.delay = delay;
+        encoder
+            .write_frame(&frame)
+            .wrap_err_with(|| format!("Could not write '{}'", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = [255, 0, 0];
+    const BLUE: Rgb = [0, 0, 255];
+
+    #[test]
+    fn from_grid_colours_each_cell() {
+        let grid = Grid::from_rows(vec![vec![true, false], vec![false, false]]).unwrap();
+        let image = Image::from_grid(&grid, |&red| if red { RED } else { BLUE });
+        assert_eq!((2, 2), (image.width(), image.height()));
+        assert_eq!(RED, image.get(0, 0));
+        assert_eq!(BLUE, image.get(1, 0));
+        assert_eq!(BLUE, image.get(0, 1));
+    }
+
+    #[test]
+    fn scaled_repeats_pixels() {
+        let mut image = Image::new(2, 1, BLUE);
+        image.set(1, 0, RED);
+        let scaled = image.scaled(3);
+        assert_eq!((6, 3), (scaled.width(), scaled.height()));
+        assert_eq!(BLUE, scaled.get(2, 2));
+        assert_eq!(RED, scaled.get(3, 0));
+    }
+
+    #[test]
+    fn png_and_gif_files() {
+        let dir = std::env::temp_dir().join(format!("image-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let image = Image::new(3, 2, RED);
+        image.write_png(&dir.join("frame.png")).unwrap();
+        write_gif(
+            &dir.join("animation.gif"),
+            &[image, Image::new(4, 1, BLUE)],
+            Duration::from_millis(100),
+        )
+        .unwrap();
+
+        let png = std::fs::read(dir.join("frame.png")).unwrap();
+        let gif = std::fs::read(dir.join("animation.gif")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        assert!(gif.starts_with(b"GIF89a"));
+        // Canvas is the size of the largest frame
+        assert_eq!([4, 0, 2, 0], gif[6..10]);
+    }
+}
@@ -0,0 +1,175 @@
+use color_eyre::{Section, SectionExt};
+use std::{
+    fmt::Display,
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A problem with the input, pointing at where in the notes it is. Once
+/// [annotated](ParseError::annotate), reports show it with a snippet of the
+/// line with the problem underlined, like:
+///
+/// ```text
+/// Error:
+///    0: Expected 'WORDS:' at notes/2024/q02p01:1:1
+///
+/// Input:
+///      |
+///    1 | WRODS:THE,OWE
+///      | ^^^^^^
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    message: String,
+    path: Option<PathBuf>,
+    /// Line number, starting at 1.
+    line: usize,
+    /// Column in characters, starting at 1.
+    column: usize,
+    /// The whole line with the problem.
+    text: String,
+    /// Number of characters to underline.
+    len: usize,
+}
+
+impl ParseError {
+    /// An error in the byte range `span` of the line `line` (starting at 1),
+    /// whose text is `text`.
+    pub fn new(message: impl Into<String>, line: usize, text: &str, span: Range<usize>) -> Self {
+        let start = span.start.min(text.len());
+        let end = span.end.clamp(start, text.len());
+        Self {
+            message: message.into(),
+            path: None,
+            line,
+            column: text[..start].chars().count() + 1,
+            text: text.to_string(),
+            len: text[start..end].chars().count().max(1),
+        }
+    }
+
+    /// An error for input that ends too early, at the line after the last.
+    pub fn end_of_input(message: impl Into<String>, line: usize) -> Self {
+        Self::new(message, line, "", 0..0)
+    }
+
+    /// The line with the problem, underlined.
+    pub fn snippet(&self) -> String {
+        let Self {
+            line,
+            column,
+            text,
+            len,
+            ..
+        } = self;
+        let gutter = " ".repeat(line.to_string().len());
+        format!(
+            "{gutter} |\n{line} | {text}\n{gutter} | {}{}",
+            " ".repeat(column - 1),
+            "^".repeat(*len)
+        )
+    }
+
+    /// Adds the snippet to the report if it is a parse error, along with the
+    /// file the input came from. The solvers only see a reader, so this is done
+    /// by whoever opened it.
+    pub fn annotate(report: color_eyre::Report, path: Option<&Path>) -> color_eyre::Report {
+        match report.downcast::<Self>() {
+            Ok(mut err) => {
+                err.path = path.map(Path::to_path_buf);
+                let snippet = err.snippet();
+                color_eyre::Report::new(err).section(snippet.header("Input:"))
+            }
+            Err(report) => report,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let Self {
+            message,
+            line,
+            column,
+            ..
+        } = self;
+        match &self.path {
+            Some(path) => write!(f, "{message} at {}:{line}:{column}", path.display()),
+            None => write!(f, "{message} at line {line}, column {column}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses the byte range `span` of a line, with an error pointing at it if it
+/// is not valid. `expected` says what should be there, such as "the length of
+/// a nail".
+pub fn parse_at<T>(
+    expected: &str,
+    line: usize,
+    text: &str,
+    span: Range<usize>,
+) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.get(span.clone())
+        .unwrap_or_default()
+        .parse()
+        .map_err(|err| ParseError::new(format!("Expected {expected}: {err}"), line, text, span))
+}
+
+/// The message of the parse error that `result` failed with, for testing how
+/// malformed inputs are reported.
+#[cfg(test)]
+pub fn parse_error_message<T: std::fmt::Debug>(result: color_eyre::Result<T>) -> String {
+    result
+        .unwrap_err()
+        .downcast::<ParseError>()
+        .expect("should be a parse error")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippet_with_caret() {
+        let err = ParseError::new("Expected 'WORDS:'", 1, "WRODS:THE,OWE", 0..6);
+        assert_eq!("Expected 'WORDS:' at line 1, column 1", err.to_string());
+        assert_eq!("  |\n1 | WRODS:THE,OWE\n  | ^^^^^^", err.snippet());
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let err = ParseError::new("Unexpected", 12, "é#x", 3..4);
+        assert_eq!(3, err.column);
+        assert!(err.snippet().ends_with("12 | é#x\n   |   ^"));
+    }
+
+    #[test]
+    fn parse_at_points_at_span() {
+        assert_eq!(Ok(12), parse_at::<i32>("a number", 1, "ab12", 2..4));
+        let err = parse_at::<i32>("a number", 3, "1x2", 0..3).unwrap_err();
+        assert_eq!((3, 1), (err.line, err.column));
+        assert!(err
+            .to_string()
+            .starts_with("Expected a number: invalid digit"));
+        assert!(err.snippet().ends_with("3 | 1x2\n  | ^^^"));
+    }
+
+    #[test]
+    fn annotate_adds_path() {
+        let report = color_eyre::Report::new(ParseError::end_of_input("Expected a blank line", 2));
+        let report = ParseError::annotate(report, Some(Path::new("notes/2024/q02p01")));
+        assert_eq!(
+            "Expected a blank line at notes/2024/q02p01:2:1",
+            report.to_string()
+        );
+        assert!(report.downcast_ref::<ParseError>().is_some());
+    }
+}
@@ -0,0 +1,110 @@
+use std::ops::RangeInclusive;
+
+/// A small, fast pseudo-random number generator (SplitMix64) for generating
+/// inputs. The same seed always gives the same numbers, on every platform, so
+/// generated inputs can be reproduced from their seed.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// The next number, from the whole range of `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to but not including `bound`, which must not be 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        // Reject the values that would make the lowest numbers more likely
+        let zone = u64::MAX - (u64::MAX - bound + 1) % bound;
+        loop {
+            let value = self.next_u64();
+            if value <= zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// A number in the inclusive range, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "range must not be empty");
+        let span = end.abs_diff(start);
+        let offset = match span.checked_add(1) {
+            Some(bound) => self.below(bound),
+            None => self.next_u64(),
+        };
+        start.wrapping_add_unsigned(offset)
+    }
+
+    /// An index into a collection of `len` items, which must not be 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// A number from 0 up to but not including 1.
+    pub fn unit(&mut self) -> f64 {
+        // The top 53 bits fill the mantissa of an f64 exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.unit() < probability
+    }
+
+    /// One of the items, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let numbers: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(numbers, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(numbers, (0..5).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn known_sequence() {
+        // First outputs of SplitMix64 seeded with 0
+        let mut rng = Rng::new(0);
+        assert_eq!(0xE220_A839_7B1D_CDAF, rng.next_u64());
+        assert_eq!(0x6E78_9E6A_A1B9_65F4, rng.next_u64());
+    }
+
+    #[test]
+    fn ranges_are_inclusive_and_bounded() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let value = rng.range(-2..=2);
+            assert!((-2..=2).contains(&value));
+            seen[(value + 2) as usize] = true;
+            assert!(rng.below(3) < 3);
+            assert!((0.0..1.0).contains(&rng.unit()));
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert_eq!(5, rng.range(5..=5));
+        let full = rng.range(i64::MIN..=i64::MAX);
+        assert!((i64::MIN..=i64::MAX).contains(&full));
+    }
+}
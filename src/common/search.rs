@@ -0,0 +1,413 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A route found by a search, from the start to a goal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route<S, C> {
+    states: Vec<S>,
+    cost: C,
+}
+
+impl<S, C: Copy> Route<S, C> {
+    /// Every state along the path, starting with the start and ending with
+    /// the goal.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The last state, which is the goal that was found.
+    pub fn goal(&self) -> &S {
+        self.states.last().expect("paths always include the start")
+    }
+
+    /// Total cost of the steps, which for a breadth-first search is the
+    /// number of steps.
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+}
+
+/// Settings for searches through states such as positions in a maze, where
+/// each state leads to some neighbouring states. States are compared by their
+/// value, so they should hold everything that affects where they lead.
+///
+/// The searches find a path to the nearest state for which `is_goal` is true,
+/// or `None` if every reachable state has been visited without finding one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Search {
+    max_visited: Option<usize>,
+}
+
+impl Search {
+    /// A search that runs until it finds a goal or runs out of states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gives up with an error after visiting this many states, rather than
+    /// running out of memory on a search that is much bigger than expected.
+    pub fn with_max_visited(self, max_visited: usize) -> Self {
+        Self {
+            max_visited: Some(max_visited),
+        }
+    }
+
+    fn visit(&self, visited: &mut usize) -> color_eyre::Result<()> {
+        *visited += 1;
+        match self.max_visited {
+            Some(max_visited) if *visited > max_visited => Err(color_eyre::eyre::eyre!(
+                "Gave up searching after visiting {max_visited} states"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Searches outwards one step at a time, for when every step costs the
+    /// same.
+    pub fn bfs<S, I>(
+        &self,
+        start: S,
+        mut neighbours: impl FnMut(&S) -> I,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> color_eyre::Result<Option<Route<S, usize>>>
+    where
+        S: Clone + Eq + Hash,
+        I: IntoIterator<Item = S>,
+    {
+        let mut parents = HashMap::from([(start.clone(), None)]);
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut visited = 0;
+        while let Some((state, steps)) = queue.pop_front() {
+            if is_goal(&state) {
+                return Ok(Some(reconstruct(&parents, state, steps)));
+            }
+            self.visit(&mut visited)?;
+            for next in neighbours(&state) {
+                if !parents.contains_key(&next) {
+                    parents.insert(next.clone(), Some(state.clone()));
+                    queue.push_back((next, steps + 1));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Number of steps to every state that can be reached from the start,
+    /// for when every step costs the same.
+    pub fn distances<S, I>(
+        &self,
+        start: S,
+        mut neighbours: impl FnMut(&S) -> I,
+    ) -> color_eyre::Result<HashMap<S, usize>>
+    where
+        S: Clone + Eq + Hash,
+        I: IntoIterator<Item = S>,
+    {
+        let mut distances = HashMap::from([(start.clone(), 0)]);
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut visited = 0;
+        while let Some((state, steps)) = queue.pop_front() {
+            self.visit(&mut visited)?;
+            for next in neighbours(&state) {
+                distances.entry(next).or_insert_with_key(|next| {
+                    queue.push_back((next.clone(), steps + 1));
+                    steps + 1
+                });
+            }
+        }
+        Ok(distances)
+    }
+
+    /// Searches the cheapest states first, for when steps have different
+    /// costs. `neighbours` gives each neighbour with the cost of the step to
+    /// it, which must not be negative.
+    pub fn dijkstra<S, C, I>(
+        &self,
+        start: S,
+        neighbours: impl FnMut(&S) -> I,
+        is_goal: impl FnMut(&S) -> bool,
+    ) -> color_eyre::Result<Option<Route<S, C>>>
+    where
+        S: Clone + Eq + Hash,
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        self.astar(start, neighbours, |_| C::default(), is_goal)
+    }
+
+    /// Like [`Search::dijkstra`], but searches the states that look closest
+    /// to a goal first. `heuristic` estimates the cost from a state to the
+    /// nearest goal, and must never overestimate it, such as the Manhattan
+    /// distance to the goal in a maze.
+    pub fn astar<S, C, I>(
+        &self,
+        start: S,
+        mut neighbours: impl FnMut(&S) -> I,
+        mut heuristic: impl FnMut(&S) -> C,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> color_eyre::Result<Option<Route<S, C>>>
+    where
+        S: Clone + Eq + Hash,
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        // The cheapest known cost of reaching each state, and where from
+        let mut best: HashMap<S, (C, Option<S>)> =
+            HashMap::from([(start.clone(), (C::default(), None))]);
+        // States are kept in a list so that the queue doesn't need them to be
+        // ordered. Ties go to the state found first.
+        let mut states = vec![start.clone()];
+        let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+        let mut visited = 0;
+
+        while let Some(Reverse((_, cost, idx))) = queue.pop() {
+            let state = states[idx].clone();
+            if best[&state].0 < cost {
+                // Already reached more cheaply since this was queued
+                continue;
+            }
+            if is_goal(&state) {
+                let parents = best
+                    .into_iter()
+                    .map(|(state, (_, parent))| (state, parent))
+                    .collect();
+                return Ok(Some(reconstruct(&parents, state, cost)));
+            }
+            self.visit(&mut visited)?;
+
+            for (next, step) in neighbours(&state) {
+                let next_cost = cost + step;
+                if best.get(&next).is_none_or(|&(known, _)| next_cost < known) {
+                    best.insert(next.clone(), (next_cost, Some(state.clone())));
+                    queue.push(Reverse((
+                        next_cost + heuristic(&next),
+                        next_cost,
+                        states.len(),
+                    )));
+                    states.push(next);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Follows the parents back from the goal to the start.
+fn reconstruct<S, C>(parents: &HashMap<S, Option<S>>, goal: S, cost: C) -> Route<S, C>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut states = vec![goal];
+    while let Some(Some(parent)) = parents.get(states.last().unwrap()) {
+        states.push(parent.clone());
+    }
+    states.reverse();
+    Route { states, cost }
+}
+
+/// Breadth-first search with no limit on the states visited. See [`Search`].
+pub fn bfs<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Route<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    Search::new()
+        .bfs(start, neighbours, is_goal)
+        .expect("searches without a limit don't fail")
+}
+
+/// Dijkstra's algorithm with no limit on the states visited. See [`Search`].
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Route<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    Search::new()
+        .dijkstra(start, neighbours, is_goal)
+        .expect("searches without a limit don't fail")
+}
+
+/// A* search with no limit on the states visited. See [`Search`].
+pub fn astar<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Route<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    Search::new()
+        .astar(start, neighbours, heuristic, is_goal)
+        .expect("searches without a limit don't fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Grid, Point, Rng};
+
+    /// A maze of walls (`#`) and open cells (`.`), with a start (`S`) and an
+    /// end (`E`).
+    struct Maze {
+        grid: Grid<char>,
+        start: Point,
+        end: Point,
+    }
+
+    impl Maze {
+        fn parse(text: &str) -> Self {
+            let grid = Grid::parse(text, Ok).unwrap();
+            let find = |target| grid.cells().find(|&(_, &c)| c == target).unwrap().0;
+            let (start, end) = (find('S'), find('E'));
+            Self { grid, start, end }
+        }
+
+        fn open(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+            self.grid
+                .neighbours4(pos)
+                .filter(|&neighbour| self.grid[neighbour] != '#')
+        }
+
+        /// Open neighbours, where stepping onto a digit costs that much and
+        /// any other cell costs 1.
+        fn weighted(&self, pos: Point) -> impl Iterator<Item = (Point, u32)> + '_ {
+            self.open(pos)
+                .map(|neighbour| (neighbour, self.grid[neighbour].to_digit(10).unwrap_or(1)))
+        }
+    }
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.####.#.
+......#E";
+
+    fn assert_connected(path: &[Point]) {
+        for step in path.windows(2) {
+            assert_eq!(1, step[0].manhattan(step[1]), "{path:?}");
+        }
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let maze = Maze::parse(MAZE);
+        let path = bfs(maze.start, |&pos| maze.open(pos), |&pos| pos == maze.end).unwrap();
+        assert_eq!(15, path.cost());
+        assert_eq!(16, path.states().len());
+        assert_eq!((&maze.start, &maze.end), (&path.states()[0], path.goal()));
+        assert_connected(path.states());
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let maze = Maze::parse("S.#E\n..#.");
+        assert_eq!(
+            None,
+            bfs(maze.start, |&pos| maze.open(pos), |&pos| pos == maze.end)
+        );
+        let distances = Search::new()
+            .distances(maze.start, |&pos| maze.open(pos))
+            .unwrap();
+        assert_eq!(4, distances.len());
+        assert_eq!(Some(&2), distances.get(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn dijkstra_avoids_expensive_cells() {
+        // Going through the 9 costs 10, and going around it costs 4
+        let maze = Maze::parse("S9E\n...");
+        let path = dijkstra(
+            maze.start,
+            |&pos| maze.weighted(pos),
+            |&pos| pos == maze.end,
+        )
+        .unwrap();
+        assert_eq!(4, path.cost());
+        assert_eq!(5, path.states().len());
+        assert_connected(path.states());
+    }
+
+    #[test]
+    fn astar_matches_dijkstra_on_random_mazes() {
+        let mut rng = Rng::new(24);
+        for _ in 0..50 {
+            let text: String = (0..12)
+                .map(|y| {
+                    let row: String = (0..12)
+                        .map(|x| match (x, y) {
+                            (0, 0) => 'S',
+                            (11, 11) => 'E',
+                            _ if rng.chance(0.3) => '#',
+                            _ => char::from_digit(1 + rng.index(9) as u32, 10).unwrap(),
+                        })
+                        .collect();
+                    row + "\n"
+                })
+                .collect();
+            let maze = Maze::parse(&text);
+            let is_end = |&pos: &Point| pos == maze.end;
+
+            let expected = dijkstra(maze.start, |&pos| maze.weighted(pos), is_end);
+            let heuristic = |&pos: &Point| pos.manhattan(maze.end) as u32;
+            let path = astar(maze.start, |&pos| maze.weighted(pos), heuristic, is_end);
+            assert_eq!(
+                expected.as_ref().map(Route::cost),
+                path.as_ref().map(Route::cost),
+                "{text}"
+            );
+            if let Some(path) = path {
+                assert_connected(path.states());
+                let cost: u32 = path.states()[1..]
+                    .iter()
+                    .map(|&pos| maze.grid[pos].to_digit(10).unwrap_or(1))
+                    .sum();
+                assert_eq!(path.cost(), cost);
+            }
+        }
+    }
+
+    #[test]
+    fn visited_limit() {
+        let maze = Maze::parse(MAZE);
+        let search = Search::new().with_max_visited(10);
+        let err = search
+            .bfs(maze.start, |&pos| maze.open(pos), |&pos| pos == maze.end)
+            .unwrap_err();
+        assert_eq!(
+            "Gave up searching after visiting 10 states",
+            err.to_string()
+        );
+        let heuristic = |&pos: &Point| pos.manhattan(maze.end);
+        assert!(search
+            .astar(
+                maze.start,
+                |&pos| maze.open(pos).map(|pos| (pos, 1)),
+                heuristic,
+                |&pos| pos == maze.end
+            )
+            .is_err());
+
+        // Searches in a small enough space finish before the limit
+        let maze = Maze::parse("S..\n..E");
+        let search = Search::new().with_max_visited(5);
+        let path = search.bfs(maze.start, |&pos| maze.open(pos), |&pos| pos == maze.end);
+        assert_eq!(3, path.unwrap().unwrap().cost());
+    }
+}
@@ -0,0 +1,264 @@
+use std::{fmt::Display, io::BufRead, str::FromStr};
+
+use super::{parse_at, Grid, ParseError};
+
+/// Notes made of sections separated by blank lines, such as a header followed
+/// by a body. Windows line endings are normalized, so blank lines are found
+/// either way.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SectionedInput {
+    text: String,
+}
+
+impl SectionedInput {
+    /// Notes with the given text.
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.replace("\r\n", "\n"),
+        }
+    }
+
+    /// Reads all of the notes.
+    pub fn read(mut input: impl BufRead) -> color_eyre::Result<Self> {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        Ok(Self::new(&text))
+    }
+
+    /// The sections in order. Runs of several blank lines, and blank lines
+    /// at the start or end, don't make empty sections.
+    pub fn sections(&self) -> Sections<'_> {
+        let mut sections = Vec::new();
+        let mut current: Option<Section> = None;
+        let mut line_count = 0;
+        for (idx, line) in self.text.lines().enumerate() {
+            line_count = idx + 1;
+            if line.trim().is_empty() {
+                sections.extend(current.take());
+            } else {
+                current
+                    .get_or_insert_with(|| Section {
+                        first_line: idx + 1,
+                        lines: Vec::new(),
+                    })
+                    .lines
+                    .push(line);
+            }
+        }
+        sections.extend(current);
+        Sections {
+            sections: sections.into_iter(),
+            end_line: line_count + 1,
+        }
+    }
+}
+
+/// The sections of a [`SectionedInput`] that have not been parsed yet.
+#[derive(Clone, Debug)]
+pub struct Sections<'a> {
+    sections: std::vec::IntoIter<Section<'a>>,
+    /// Line number just past the end of the input, for errors about sections
+    /// that are missing.
+    end_line: usize,
+}
+
+impl<'a> Sections<'a> {
+    /// The next section, or an error saying what was `expected` instead of
+    /// the end of the input.
+    pub fn expect(&mut self, expected: &str) -> Result<Section<'a>, ParseError> {
+        self.sections
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(format!("Expected {expected}"), self.end_line))
+    }
+
+    /// Checks that every section has been parsed.
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        match self.sections.next() {
+            None => Ok(()),
+            Some(section) => {
+                let (line_num, line) = section.first();
+                Err(ParseError::new(
+                    "Expected the end of the input",
+                    line_num,
+                    line,
+                    0..line.len(),
+                ))
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        self.sections.next()
+    }
+}
+
+/// A run of lines that are not blank.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section<'a> {
+    /// Line number of the first line in the notes, starting at 1.
+    first_line: usize,
+    /// Never empty.
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    /// Each line with its line number in the notes.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        (self.first_line..).zip(self.lines.iter().copied())
+    }
+
+    fn first(&self) -> (usize, &'a str) {
+        (self.first_line, self.lines[0])
+    }
+
+    /// The lines, each followed by a newline.
+    pub fn text(&self) -> String {
+        self.lines.iter().flat_map(|line| [line, "\n"]).collect()
+    }
+
+    /// The comma separated values of a header line like `KEY:a,b,c`, which
+    /// must be the only line of the section. `value` says what each value is,
+    /// for the error if one is empty.
+    pub fn header(&self, key: &str, value: &str) -> Result<Vec<&'a str>, ParseError> {
+        if let Some((line_num, line)) = self.lines().nth(1) {
+            return Err(ParseError::new(
+                "Expected a blank line",
+                line_num,
+                line,
+                0..line.len(),
+            ));
+        }
+
+        let (line_num, line) = self.first();
+        let line = line.trim_ascii_end();
+        let Some(values) = line
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+        else {
+            return Err(ParseError::new(
+                format!("Expected '{key}:'"),
+                line_num,
+                line,
+                0..key.len() + 1,
+            ));
+        };
+
+        let mut start = key.len() + 1;
+        let mut result = Vec::new();
+        for value_text in values.split(',') {
+            if value_text.is_empty() {
+                let message = format!("Expected {value}");
+                return Err(ParseError::new(message, line_num, line, start..start + 1));
+            }
+            result.push(value_text);
+            start += value_text.len() + 1;
+        }
+        Ok(result)
+    }
+
+    /// A number on each line. `number` says what each number is, for the
+    /// error if one is not valid.
+    pub fn numbers<T>(&self, number: &str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.lines()
+            .map(|(line_num, line)| {
+                let line = line.trim_ascii_end();
+                parse_at(number, line_num, line, 0..line.len())
+            })
+            .collect()
+    }
+
+    /// A grid with a cell for each character, like [`Grid::parse`].
+    pub fn grid<T>(&self, cell: impl Fn(char) -> Result<T, String>) -> Result<Grid<T>, ParseError> {
+        Grid::parse_lines(self.lines(), cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_on_blank_lines() {
+        let input = SectionedInput::new("\r\nKEY:a,b\r\n\r\n  \r\n1\r\n2\r\n\r\n#.\n.#\n\n");
+        let sections: Vec<Section> = input.sections().collect();
+        assert_eq!(3, sections.len());
+        assert_eq!(
+            vec![(2, "KEY:a,b")],
+            sections[0].lines().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(5, "1"), (6, "2")],
+            sections[1].lines().collect::<Vec<_>>()
+        );
+        assert_eq!("#.\n.#\n", sections[2].text());
+    }
+
+    #[test]
+    fn headers_numbers_and_grids() {
+        let input = SectionedInput::new("KEY:a,bc\n\n1\n-2 \n\n#.\n.#\n");
+        let mut sections = input.sections();
+        let header = sections.expect("a header").unwrap();
+        assert_eq!(Ok(vec!["a", "bc"]), header.header("KEY", "a value"));
+        let numbers = sections.expect("numbers").unwrap();
+        assert_eq!(Ok(vec![1, -2]), numbers.numbers::<i32>("a number"));
+        let grid = sections
+            .expect("a grid")
+            .unwrap()
+            .grid(|c| Ok(c == '#'))
+            .unwrap();
+        assert_eq!("truefalse\nfalsetrue\n", grid.to_string());
+        assert_eq!(Ok(()), sections.finish());
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let error = |text: &str, parse: fn(Sections) -> Result<(), ParseError>| {
+            parse(SectionedInput::new(text).sections())
+                .unwrap_err()
+                .to_string()
+        };
+        let header = |mut sections: Sections| {
+            sections.expect("a header")?.header("KEY", "a value")?;
+            sections.finish()
+        };
+        assert_eq!(
+            "Expected 'KEY:' at line 1, column 1",
+            error("KYE:a", header)
+        );
+        assert_eq!(
+            "Expected a value at line 2, column 7",
+            error("\nKEY:a,,b", header)
+        );
+        assert_eq!(
+            "Expected a blank line at line 2, column 1",
+            error("KEY:a\nb", header)
+        );
+        assert_eq!(
+            "Expected a header at line 3, column 1",
+            error("\n\n", header)
+        );
+        assert_eq!(
+            "Expected the end of the input at line 3, column 1",
+            error("KEY:a\n\nb", header)
+        );
+
+        let numbers = |mut sections: Sections| {
+            sections
+                .expect("numbers")?
+                .numbers::<u8>("a small number")
+                .map(drop)
+        };
+        assert_eq!(
+            "Expected a small number: invalid digit found in string at line 2, column 1",
+            error("1\nx\r\n", numbers)
+        );
+    }
+}
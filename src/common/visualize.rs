@@ -0,0 +1,328 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use color_eyre::eyre::WrapErr;
+
+use super::{write_gif, Image};
+
+/// Something that can be drawn as one frame of a visualization, such as the
+/// state of a simulation after each step.
+pub trait Frame {
+    /// The frame as plain text.
+    fn text(&self) -> String;
+
+    /// The frame with ANSI colours, for terminals. Defaults to the plain text.
+    fn ansi(&self) -> String {
+        self.text()
+    }
+
+    /// The frame as an image with a pixel for each cell, if it can be drawn as
+    /// one.
+    fn image(&self) -> Option<Image> {
+        None
+    }
+}
+
+/// Receives the frames of a simulation as a solver produces them. Frames are
+/// only rendered if the visualizer shows them, so pushing frames is cheap when
+/// visualization is off.
+pub trait Visualizer {
+    /// Shows the next frame, with a short label such as `round 3`.
+    fn frame(&mut self, label: &str, frame: &dyn Frame) -> color_eyre::Result<()>;
+
+    /// Called after the last frame.
+    fn finish(&mut self) -> color_eyre::Result<()> {
+        Ok(())
+    }
+}
+
+/// How frames should be shown, as chosen on the command line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Visualize {
+    /// Discard the frames.
+    #[default]
+    Off,
+    /// Print each frame as plain text.
+    Text,
+    /// Print each frame with ANSI colours.
+    Colour,
+    /// Redraw each frame in place in the terminal, waiting between frames.
+    Animate(Duration),
+    /// Append each frame as plain text to a file.
+    Record(PathBuf),
+    /// Save each frame as a PNG image, and all of them as an animated GIF, in
+    /// a directory for each simulation.
+    Export {
+        /// The directory containing the directory for each simulation.
+        dir: PathBuf,
+        /// Time between frames of the GIF.
+        delay: Duration,
+    },
+}
+
+impl Visualize {
+    /// Records frames to the file, replacing anything already in it.
+    pub fn record(path: &Path) -> color_eyre::Result<Self> {
+        File::create(path).wrap_err_with(|| format!("Could not create '{}'", path.display()))?;
+        Ok(Self::Record(path.to_path_buf()))
+    }
+
+    /// How to show the frames of an example, so that they don't overwrite the
+    /// frames exported for the notes.
+    pub fn for_example(&self, example: &str) -> Self {
+        match self {
+            Self::Export { dir, delay } => Self::Export {
+                dir: dir.join("examples").join(example),
+                delay: *delay,
+            },
+            other => other.clone(),
+        }
+    }
+
+    /// Creates a visualizer for one simulation, named for example after the
+    /// part being solved.
+    pub fn visualizer(&self, name: &str) -> color_eyre::Result<Box<dyn Visualizer>> {
+        Ok(match self {
+            Self::Off => Box::new(Discard),
+            Self::Text => Box::new(TextVisualizer::new(io::stdout())),
+            Self::Colour => Box::new(TextVisualizer::new(io::stdout()).with_colour()),
+            Self::Animate(delay) => Box::new(AnsiAnimation::new(*delay)),
+            Self::Record(path) => Box::new(TextVisualizer::new(
+                OpenOptions::new()
+                    .append(true)
+                    .open(path)
+                    .wrap_err_with(|| format!("Could not open '{}'", path.display()))?,
+            )),
+            Self::Export { dir, delay } => Box::new(ExportFrames::new(&dir.join(name), *delay)?),
+        })
+    }
+}
+
+struct Discard;
+
+impl Visualizer for Discard {
+    fn frame(&mut self, _label: &str, _frame: &dyn Frame) -> color_eyre::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes each frame as plain text under a numbered heading.
+pub struct TextVisualizer<W> {
+    output: W,
+    frames: usize,
+    colour: bool,
+}
+
+impl<W: Write> TextVisualizer<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            frames: 0,
+            colour: false,
+        }
+    }
+
+    /// Writes the frames with ANSI colours instead of as plain text.
+    pub fn with_colour(self) -> Self {
+        Self {
+            colour: true,
+            ..self
+        }
+    }
+}
+
+impl<W: Write> Visualizer for TextVisualizer<W> {
+    fn frame(&mut self, label: &str, frame: &dyn Frame) -> color_eyre::Result<()> {
+        // Written in one go so that frames from parallel parts don't interleave
+        let frame = if self.colour {
+            frame.ansi()
+        } else {
+            frame.text()
+        };
+        let text = format!("--- frame {} ({label}) ---\n{frame}\n", self.frames);
+        self.output.write_all(text.as_bytes())?;
+        self.output.flush()?;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+/// Redraws each frame over the previous one in the terminal.
+pub struct AnsiAnimation {
+    delay: Duration,
+    frames: usize,
+}
+
+impl AnsiAnimation {
+    pub fn new(delay: Duration) -> Self {
+        Self { delay, frames: 0 }
+    }
+}
+
+impl Visualizer for AnsiAnimation {
+    fn frame(&mut self, label: &str, frame: &dyn Frame) -> color_eyre::Result<()> {
+        if self.frames > 0 {
+            thread::sleep(self.delay);
+        }
+        let mut stdout = io::stdout().lock();
+        // Move to the top left and clear the screen
+        write!(stdout, "\x1b[H\x1b[2J")?;
+        writeln!(stdout, "\x1b[1mframe {} ({label})\x1b[0m", self.frames)?;
+        writeln!(stdout, "{}", frame.ansi())?;
+        stdout.flush()?;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+/// Saves each frame that can be drawn as an image to `frameNNNN.png`, and all
+/// of them to `animation.gif` when the simulation finishes. Frames from an
+/// earlier run are removed first, so that a shorter run doesn't leave some of
+/// them behind.
+pub struct ExportFrames {
+    dir: PathBuf,
+    delay: Duration,
+    scale: Option<usize>,
+    images: Vec<Image>,
+}
+
+impl ExportFrames {
+    pub fn new(dir: &Path, delay: Duration) -> color_eyre::Result<Self> {
+        fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("Could not create '{}'", dir.display()))?;
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("");
+            let is_frame = name.starts_with("frame") && name.ends_with(".png");
+            if is_frame || name == "animation.gif" {
+                fs::remove_file(&path)
+                    .wrap_err_with(|| format!("Could not remove '{}'", path.display()))?;
+            }
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            delay,
+            scale: None,
+            images: Vec::new(),
+        })
+    }
+}
+
+impl Visualizer for ExportFrames {
+    fn frame(&mut self, _label: &str, frame: &dyn Frame) -> color_eyre::Result<()> {
+        let Some(image) = frame.image() else {
+            return Ok(());
+        };
+        // Every frame uses the same scale, so that they line up in the GIF
+        let scale = *self.scale.get_or_insert_with(|| {
+            let size = image.width().max(image.height()).max(1);
+            (512 / size).clamp(1, 16)
+        });
+        let image = image.scaled(scale);
+        image.write_png(&self.dir.join(format!("frame{:04}.png", self.images.len())))?;
+        self.images.push(image);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> color_eyre::Result<()> {
+        if self.images.is_empty() {
+            return Ok(());
+        }
+        write_gif(&self.dir.join("animation.gif"), &self.images, self.delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(usize);
+
+    impl Frame for Counter {
+        fn text(&self) -> String {
+            "#".repeat(self.0)
+        }
+
+        fn image(&self) -> Option<Image> {
+            Some(Image::new(self.0, 1, [255; 3]))
+        }
+    }
+
+    #[test]
+    fn text_visualizer_numbers_frames() {
+        let mut output = Vec::new();
+        let mut visualizer = TextVisualizer::new(&mut output);
+        visualizer.frame("start", &Counter(1)).unwrap();
+        visualizer.frame("step 1", &Counter(3)).unwrap();
+        assert_eq!(
+            "--- frame 0 (start) ---\n#\n--- frame 1 (step 1) ---\n###\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn record_appends_to_file() {
+        let path = std::env::temp_dir().join(format!("visualize-{}.txt", std::process::id()));
+        let visualize = Visualize::record(&path).unwrap();
+        visualize
+            .visualizer("q01p01")
+            .unwrap()
+            .frame("a", &Counter(2))
+            .unwrap();
+        visualize
+            .visualizer("q01p01")
+            .unwrap()
+            .frame("b", &Counter(1))
+            .unwrap();
+        let recorded = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            "--- frame 0 (a) ---\n##\n--- frame 0 (b) ---\n#\n",
+            recorded
+        );
+    }
+
+    #[test]
+    fn export_frames_as_images() {
+        let dir = std::env::temp_dir().join(format!("export-{}", std::process::id()));
+        let visualize = Visualize::Export {
+            dir: dir.clone(),
+            delay: Duration::from_millis(50),
+        };
+        let mut visualizer = visualize.visualizer("q03p01").unwrap();
+        for step in 0..3 {
+            visualizer.frame("step", &Counter(step + 1)).unwrap();
+        }
+        visualizer.finish().unwrap();
+        // A shorter run replaces the frames of the longer one
+        let mut visualizer = visualize.visualizer("q03p01").unwrap();
+        visualizer.frame("start", &Counter(2)).unwrap();
+        visualizer.frame("step 1", &Counter(1)).unwrap();
+        visualizer.finish().unwrap();
+        let mut example = visualize.for_example("1").visualizer("q03p01").unwrap();
+        example.frame("start", &Counter(1)).unwrap();
+        example.finish().unwrap();
+
+        let mut files: Vec<String> = fs::read_dir(dir.join("q03p01"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        let example_dir = dir.join("examples").join("1").join("q03p01");
+        let example_frames = fs::read_dir(example_dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            vec!["animation.gif", "frame0000.png", "frame0001.png"],
+            files
+        );
+        assert_eq!(2, example_frames);
+    }
+}
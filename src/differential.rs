@@ -0,0 +1,186 @@
+//! Differential testing of the quests against slow but obviously correct
+//! reference solvers, on inputs from the quests' generators.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{
+    common::{Answer, Config, Part, Rng},
+    quests::Quest,
+};
+
+/// Number of generated inputs to try for each part.
+const CASES: u64 = 1000;
+
+/// Largest `size` to generate inputs with. Bugs almost always show up in small
+/// inputs, and small inputs keep the reference solvers fast.
+const MAX_SIZE: usize = 12;
+
+/// Asserts that a quest part gives the same answers as a reference solver on
+/// generated inputs. If they disagree, the input is shrunk to the smallest one
+/// that they still disagree on, which is shown in the panic message. Inputs
+/// that the reference solver rejects are skipped.
+pub fn assert_matches_reference(
+    quest: &dyn Quest,
+    part: Part,
+    config: &Config,
+    reference: fn(&str) -> color_eyre::Result<Answer>,
+) {
+    for case in 0..CASES {
+        let seed = case << 8 | u64::from(usize::from(part) as u8);
+        let size = 1 + case as usize % MAX_SIZE;
+        let input = quest
+            .generate(part, size, &mut Rng::new(seed))
+            .expect("quest should have an input generator");
+
+        if let Some(problem) = disagreement(quest, part, config, reference, &input) {
+            let smallest = shrink(&input, |input| {
+                disagreement(quest, part, config, reference, input).is_some()
+            });
+            let problem =
+                disagreement(quest, part, config, reference, &smallest).unwrap_or(problem);
+            panic!(
+                "quest {} part {part} disagrees with the reference solver on input \
+                 generated with seed {seed} and size {size}\n\
+                 smallest input they disagree on: {problem}\n{smallest}",
+                quest.number()
+            );
+        }
+    }
+}
+
+/// Describes how the quest's answer differs from the reference solver's, or
+/// returns `None` if they agree or the reference solver rejects the input.
+fn disagreement(
+    quest: &dyn Quest,
+    part: Part,
+    config: &Config,
+    reference: fn(&str) -> color_eyre::Result<Answer>,
+    input: &str,
+) -> Option<String> {
+    // A reference solver that panics rejects the input too, which can happen
+    // to the odd inputs that shrinking produces
+    let expected = panic::catch_unwind(|| reference(input)).ok()?.ok()?;
+    let solution = panic::catch_unwind(AssertUnwindSafe(|| {
+        quest.solve(part, &mut input.as_bytes(), config)
+    }));
+    match solution {
+        Ok(Ok(answer)) if answer == expected => None,
+        Ok(Ok(answer)) => Some(format!("expected {expected}, got {answer}")),
+        Ok(Err(err)) => Some(format!("expected {expected}, got error: {err:#}")),
+        Err(_) => Some(format!("expected {expected}, but it panicked")),
+    }
+}
+
+/// Removes as much of the input as possible while it still fails: first runs
+/// of lines, then runs of characters, halving the run length each time.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut smallest = input.to_string();
+    'shrinking: loop {
+        for split in [split_lines, split_chars] {
+            let pieces = split(&smallest);
+            let mut len = pieces.len().div_ceil(2);
+            while len > 0 {
+                for start in (0..pieces.len()).step_by(len) {
+                    let candidate: String = pieces[..start]
+                        .iter()
+                        .chain(pieces.get(start + len..).unwrap_or_default())
+                        .copied()
+                        .collect();
+                    if fails(&candidate) {
+                        smallest = candidate;
+                        continue 'shrinking;
+                    }
+                }
+                len /= 2;
+            }
+        }
+        return smallest;
+    }
+}
+
+fn split_lines(input: &str) -> Vec<&str> {
+    input.split_inclusive('\n').collect()
+}
+
+fn split_chars(input: &str) -> Vec<&str> {
+    input
+        .char_indices()
+        .map(|(idx, c)| &input[idx..idx + c.len_utf8()])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Event;
+    use std::io::BufRead;
+
+    #[test]
+    fn shrink_to_smallest_failing_input() {
+        let fails = |input: &str| input.contains('B') && input.contains('C');
+        assert_eq!("BC", shrink("AAB\nxx\nCA\nD", fails));
+        assert_eq!(
+            "C\nB",
+            shrink("C\nAAB\n", |input| input.contains("C\n") && fails(input))
+        );
+    }
+
+    /// Counts the `A`s, but forgets the last one.
+    struct CountA;
+
+    impl Quest for CountA {
+        fn number(&self) -> usize {
+            99
+        }
+
+        fn title(&self) -> &'static str {
+            "Count A"
+        }
+
+        fn part_one(
+            &self,
+            input: &mut dyn BufRead,
+            _config: &Config,
+        ) -> color_eyre::Result<Answer> {
+            let mut text = String::new();
+            input.read_to_string(&mut text)?;
+            let count = text.matches('A').count();
+            Ok(if count > 1 { count - 1 } else { count }.into())
+        }
+
+        fn part_two(
+            &self,
+            _input: &mut dyn BufRead,
+            _config: &Config,
+        ) -> color_eyre::Result<Answer> {
+            Ok("todo".into())
+        }
+
+        fn part_three(
+            &self,
+            _input: &mut dyn BufRead,
+            _config: &Config,
+        ) -> color_eyre::Result<Answer> {
+            Ok("todo".into())
+        }
+
+        fn generate(&self, _part: Part, size: usize, rng: &mut Rng) -> Option<String> {
+            Some((0..size).map(|_| *rng.choose(&['A', 'B', '\n'])).collect())
+        }
+    }
+
+    #[test]
+    fn disagreement_is_shrunk() {
+        let config = Config::test(Event::Year(2024));
+        let result = panic::catch_unwind(|| {
+            assert_matches_reference(&CountA, Part::One, &config, |input| {
+                Ok(input.matches('A').count().into())
+            })
+        });
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(
+            message.ends_with("smallest input they disagree on: expected 2, got 1\nAA"),
+            "{message}"
+        );
+    }
+}
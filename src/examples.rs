@@ -0,0 +1,94 @@
+//! Examples from the puzzle descriptions, with their expected answers.
+
+use std::{fs, path::Path};
+
+use color_eyre::eyre::WrapErr;
+
+use crate::common::{Answer, InputSource, Part};
+
+/// An example from a puzzle description, stored in the notes directory as
+/// `examples/q<quest>p<part>/<name>.in` with the expected answer in
+/// `<name>.answer` next to it.
+#[derive(Debug)]
+pub struct Example {
+    /// The file name without the extension.
+    pub name: String,
+    pub input: InputSource,
+    /// The answer given for the example in the puzzle description.
+    pub expected: Answer,
+}
+
+/// Loads the examples for a quest part, sorted by name. Parts without an
+/// examples directory have no examples.
+pub fn load(notes_dir: &Path, quest_number: usize, part: Part) -> color_eyre::Result<Vec<Example>> {
+    let dir = notes_dir.join("examples").join(part.key(quest_number));
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut examples = Vec::new();
+    for entry in
+        fs::read_dir(&dir).wrap_err_with(|| format!("Could not read '{}'", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "in") {
+            continue;
+        }
+
+        let answer_path = path.with_extension("answer");
+        let expected = fs::read_to_string(&answer_path)
+            .wrap_err_with(|| format!("Could not read '{}'", answer_path.display()))?;
+        examples.push(Example {
+            name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+            input: InputSource::File(path),
+            expected: expected.trim().parse()?,
+        });
+    }
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Asserts that a quest part solves all of its examples, and that it has at
+/// least one. The examples are taken from the notes of `config.event`.
+#[cfg(test)]
+pub fn assert_examples(
+    quest: &dyn crate::quests::Quest,
+    part: Part,
+    config: &crate::common::Config,
+) {
+    let notes_dir = config.event.notes_dir(Path::new("notes"));
+    let examples = load(&notes_dir, quest.number(), part).unwrap();
+    assert!(
+        !examples.is_empty(),
+        "no examples for quest {} part {part}",
+        quest.number()
+    );
+    for example in examples {
+        let mut input = example.input.reader().unwrap();
+        let answer = quest
+            .solve(part, &mut input, config)
+            .map_err(|err| example.input.annotate_error(err))
+            .unwrap();
+        assert_eq!(example.expected, answer, "example {}", example.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_examples() {
+        let examples = load(Path::new("notes/2024"), 2, Part::Two).unwrap();
+        assert_eq!(1, examples.len());
+        assert_eq!("1", examples[0].name);
+        assert_eq!(Answer::Int(42), examples[0].expected);
+    }
+
+    #[test]
+    fn load_missing_examples() {
+        assert!(load(Path::new("notes/2024"), 4, Part::Two)
+            .unwrap()
+            .is_empty());
+    }
+}
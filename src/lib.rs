@@ -0,0 +1,27 @@
+//! Solutions to the [Everybody Codes](https://everybody.codes) puzzles.
+//!
+//! Each solved quest implements [`quests::Quest`] and is listed in a registry
+//! generated from the modules in `src/quests/`, so quests can be looked up by
+//! event and number:
+//!
+//! ```
+//! use everybody_codes::{
+//!     common::{Config, Event, Part},
+//!     quests,
+//! };
+//!
+//! let config = Config::new(Event::Year(2024));
+//! let answer = quests::solve(1, Part::One, "ABBAC".as_bytes(), &config)?;
+//! assert_eq!("5", answer);
+//! # Ok::<(), color_eyre::Report>(())
+//! ```
+//!
+//! The `everybody-codes` binary is a command line interface to this library.
+
+pub mod answers;
+pub mod common;
+#[cfg(test)]
+mod differential;
+pub mod examples;
+pub mod pool;
+pub mod quests;
@@ -0,0 +1,148 @@
+use std::io::Write;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Logs to stderr, with levels chosen per module by [`Filter`].
+struct Logger {
+    filter: Filter,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let module = record.target().rsplit("::").next().unwrap_or_default();
+        let _ = writeln!(
+            std::io::stderr().lock(),
+            "\x1b[2m{:5} {module}:\x1b[0m {}",
+            record.level(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+/// Sets up logging at the level given by the number of `-v` flags, adjusted by
+/// the `--log` directives.
+pub fn init(verbosity: u8, directives: Option<&str>) -> color_eyre::Result<()> {
+    let default = match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    let filter = Filter::parse(default, directives.unwrap_or_default())?;
+    log::set_max_level(filter.max_level());
+    log::set_boxed_logger(Box::new(Logger { filter }))?;
+    Ok(())
+}
+
+/// Log levels for modules, from directives such as `quest03=trace` or
+/// `y2024::quest03=debug`. A directive without a module sets the level for
+/// everything else.
+#[derive(Debug, PartialEq)]
+struct Filter {
+    default: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    fn parse(default: LevelFilter, directives: &str) -> color_eyre::Result<Self> {
+        let mut filter = Self {
+            default,
+            modules: Vec::new(),
+        };
+        for directive in directives.split(',').map(str::trim) {
+            if directive.is_empty() {
+                continue;
+            }
+            let parse_level = |level: &str| {
+                level.parse().map_err(|_| {
+                    color_eyre::eyre::eyre!("Invalid log level '{level}' in '{directive}'")
+                })
+            };
+            match directive.split_once('=') {
+                Some((module, level)) => filter
+                    .modules
+                    .push((module.to_string(), parse_level(level)?)),
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+        // Longest module names are the most specific, so are checked first
+        filter
+            .modules
+            .sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+        Ok(filter)
+    }
+
+    fn level(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .find(|(module, _)| matches_module(target, module))
+            .map_or(self.default, |&(_, level)| level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|&(_, level)| level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+/// Whether the module path contains the (possibly partial) module path, e.g.
+/// `everybody_codes::quests::y2024::quest03` contains `quest03`.
+fn matches_module(target: &str, module: &str) -> bool {
+    let target: Vec<&str> = target.split("::").collect();
+    let module: Vec<&str> = module.split("::").collect();
+    target.windows(module.len()).any(|window| window == module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUEST03: &str = "everybody_codes::quests::y2024::quest03";
+
+    #[test]
+    fn module_directives() {
+        let filter = Filter::parse(LevelFilter::Warn, "quest03=trace").unwrap();
+        assert_eq!(LevelFilter::Trace, filter.level(QUEST03));
+        assert_eq!(
+            LevelFilter::Warn,
+            filter.level("everybody_codes::commands::run")
+        );
+        assert_eq!(LevelFilter::Trace, filter.max_level());
+    }
+
+    #[test]
+    fn most_specific_directive_wins() {
+        let filter =
+            Filter::parse(LevelFilter::Warn, "y2024::quest03=debug, y2024=info, error").unwrap();
+        assert_eq!(LevelFilter::Debug, filter.level(QUEST03));
+        assert_eq!(
+            LevelFilter::Info,
+            filter.level("everybody_codes::quests::y2024::quest01")
+        );
+        assert_eq!(LevelFilter::Error, filter.level("everybody_codes::pool"));
+    }
+
+    #[test]
+    fn partial_names_do_not_match() {
+        assert!(matches_module(QUEST03, "quests::y2024"));
+        assert!(!matches_module(QUEST03, "quest0"));
+        assert!(!matches_module(QUEST03, "quest03::y2024"));
+    }
+
+    #[test]
+    fn invalid_level() {
+        assert!(Filter::parse(LevelFilter::Warn, "quest03=loud").is_err());
+        assert!(Filter::parse(LevelFilter::Warn, "loud").is_err());
+    }
+}
@@ -1,8 +1,12 @@
-use common::{file_reader, Config, Part};
-use quests::{solve, NUM_QUESTS};
+use cli::{Cli, Command, OutputFormat};
+use everybody_codes::{
+    common::{Config, Visualize},
+    quests,
+};
 
-mod common;
-mod quests;
+mod cli;
+mod commands;
+mod logging;
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
@@ -11,52 +15,48 @@ fn main() -> color_eyre::Result<()> {
         std::env::set_var("RUST_BACKTRACE", "full");
     }
 
-    let mut args: Vec<String> = std::env::args().collect();
+    let cli = Cli::parse_args();
+    logging::init(cli.verbose, cli.log.as_deref())?;
 
-    let verbose = if let Some(verbose_idx) = args
-        .iter()
-        .position(|arg| arg == "-v" || arg == "--verbose")
-    {
-        args.remove(verbose_idx);
-        true
-    } else {
-        false
+    let event = cli.event.or_else(quests::latest_event).ok_or_else(|| {
+        color_eyre::eyre::eyre!("No events have been solved yet, choose one with --event")
+    })?;
+    let config = Config {
+        event,
+        visualize: cli.visualize()?,
     };
-
-    let config = Config { verbose };
-
-    let quest_numbers = if args.len() > 1 {
-        vec![args[1]
-            .parse::<usize>()
-            .expect("Quest number must be an integer")]
-    } else {
-        (1..=NUM_QUESTS).collect()
-    };
-
-    let part_number = args.get(2).map(|num| {
-        num.parse::<usize>()
-            .expect("Part number must be an integer")
-    });
-    let parts = if let Some(part_number) = part_number {
-        vec![part_number.try_into()?]
-    } else {
-        vec![Part::One, Part::Two, Part::Three]
+    let notes_dir = event.notes_dir(&cli.notes_dir);
+    // Drawing frames would be timed along with the solutions, and checking
+    // only needs the answers
+    let quiet_config = || {
+        if !matches!(config.visualize, Visualize::Off) {
+            log::warn!("Visualizing is only done by the run command");
+        }
+        Config::new(event)
     };
 
-    let input_path = args.get(3);
-
-    for &quest_number in quest_numbers.iter() {
-        println!("\x1b[1mQuest {quest_number}\x1b[0m");
-        for &part in parts.iter() {
-            let input = file_reader(
-                &input_path
-                    .cloned()
-                    .unwrap_or_else(|| part.default_input_path(quest_number)),
-            )?;
-            let solution = solve(quest_number, part, input, &config)?;
-            println!("  Part {part}: {solution}");
+    let command = cli.command.unwrap_or(Command::Run(cli.run));
+    if let Command::Run(args) = &command {
+        // These print frames to stdout, where they would corrupt the JSON
+        let prints_frames = matches!(
+            config.visualize,
+            Visualize::Text | Visualize::Colour | Visualize::Animate(_)
+        );
+        if prints_frames && args.format != OutputFormat::Text {
+            return Err(color_eyre::eyre::eyre!(
+                "--visualize only works with --format text, use --record or --export-frames instead"
+            ));
         }
     }
 
-    Ok(())
+    match command {
+        Command::Run(args) if args.watch => commands::watch(&args, &notes_dir, &config),
+        Command::Run(args) => commands::run(&args, &notes_dir, &config),
+        Command::Check(selection) => commands::check(&selection, &notes_dir, &quiet_config()),
+        Command::Bench(args) => commands::bench(&args, &notes_dir, &quiet_config()),
+        Command::Gen(args) => commands::generate(&args, &config),
+        Command::New { quest, title } => commands::new(event, quest, &title, &notes_dir),
+        Command::List => commands::list(&cli.notes_dir),
+        Command::Info { quest } => commands::info(event, quest, &notes_dir),
+    }
 }
@@ -0,0 +1,128 @@
+//! A small thread pool for solving parts in parallel.
+
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Number of worker threads to use when the user asks for "as many as
+/// possible".
+pub fn available_jobs() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Runs `work` on each item using up to `jobs` worker threads, passing the
+/// results to `output` in the same order as `items`. Each result is handed
+/// over as soon as it and all results before it are ready.
+///
+/// If `output` returns an error, no new items are started and the error is
+/// returned once the items already in progress have finished.
+pub fn for_each_ordered<T, R, E>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut output: impl FnMut(R) -> Result<(), E>,
+) -> Result<(), E>
+where
+    T: Sync,
+    R: Send,
+{
+    let next_item = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next_item, cancelled, work) = (&next_item, &cancelled, &work);
+            scope.spawn(move || {
+                while !cancelled.load(Ordering::Relaxed) {
+                    let idx = next_item.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(idx) else {
+                        break;
+                    };
+                    if sender.send((idx, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_output = 0;
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&next_output) {
+                next_output += 1;
+                if let Err(err) = output(result) {
+                    cancelled.store(true, Ordering::Relaxed);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn results_are_in_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut results = Vec::new();
+        for_each_ordered(
+            &items,
+            4,
+            |&item| {
+                // Make earlier items finish later
+                thread::sleep(Duration::from_millis(20 - item));
+                item * 2
+            },
+            |result| {
+                results.push(result);
+                Ok::<_, ()>(())
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            items.iter().map(|item| item * 2).collect::<Vec<_>>(),
+            results
+        );
+    }
+
+    #[test]
+    fn error_stops_output() {
+        let items: Vec<u32> = (0..100).collect();
+        let mut results = Vec::new();
+        let result = for_each_ordered(
+            &items,
+            2,
+            |&item| item,
+            |result| {
+                if result == 5 {
+                    return Err("five");
+                }
+                results.push(result);
+                Ok(())
+            },
+        );
+        assert_eq!(Err("five"), result);
+        assert_eq!(vec![0, 1, 2, 3, 4], results);
+    }
+
+    #[test]
+    fn no_items() {
+        let items: Vec<u32> = Vec::new();
+        let result = for_each_ordered(&items, 8, |&item| item, |_| Err(()));
+        assert_eq!(Ok(()), result);
+    }
+}
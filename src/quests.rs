@@ -1,27 +1,393 @@
-use std::io::BufRead;
+//! The [`Quest`] trait and the registry of solved quests.
 
-use crate::common::{Config, Part};
+use std::{
+    any::Any,
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{BufRead, Cursor},
+    sync::{Arc, Mutex},
+};
 
-mod quest01;
-mod quest02;
-mod quest03;
-mod quest04;
+use crate::common::{Answer, Config, Event, Part, Rng};
 
-pub const NUM_QUESTS: usize = 4;
+// Declares a module for each `src/quests/<event>/questNN.rs` and lists them in `QUESTS`
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
 
+/// Input that has been parsed by [`Quest::parse`], shared between parts.
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// What the default [`Quest::parse`] returns: the raw input, for the `part_*`
+/// methods to parse themselves.
+struct Unparsed(Vec<u8>);
+
+/// A solved quest. Each quest module implements this for a `QuestNN` struct,
+/// which the build script then adds to the registry.
+///
+/// Quests whose parts read their input differently implement the `part_*`
+/// methods. Quests whose parts share an input format implement
+/// [`Quest::parse`] and [`Quest::solve_parsed`] instead, so that the input is
+/// only parsed once, and the `part_*` methods then use those. Parts that are
+/// implemented neither way fail with an error.
+pub trait Quest: Sync {
+    /// The quest's number within its event, starting at 1.
+    fn number(&self) -> usize;
+
+    /// The title of the puzzle description.
+    fn title(&self) -> &'static str;
+
+    /// Solves part one, by default using [`Quest::parse`] and
+    /// [`Quest::solve_parsed`].
+    fn part_one(&self, input: &mut dyn BufRead, config: &Config) -> color_eyre::Result<Answer> {
+        solve_part(self, Part::One, input, config)
+    }
+
+    /// Solves part two, by default using [`Quest::parse`] and
+    /// [`Quest::solve_parsed`].
+    fn part_two(&self, input: &mut dyn BufRead, config: &Config) -> color_eyre::Result<Answer> {
+        solve_part(self, Part::Two, input, config)
+    }
+
+    /// Solves part three, by default using [`Quest::parse`] and
+    /// [`Quest::solve_parsed`].
+    fn part_three(&self, input: &mut dyn BufRead, config: &Config) -> color_eyre::Result<Answer> {
+        solve_part(self, Part::Three, input, config)
+    }
+
+    /// Solves a part by parsing the input and solving the parsed input.
+    fn solve(
+        &self,
+        part: Part,
+        input: &mut dyn BufRead,
+        config: &Config,
+    ) -> color_eyre::Result<Answer> {
+        self.solve_parsed(part, &self.parse(input)?, config)
+    }
+
+    /// Parses the input into a value that all three parts can share. By
+    /// default this only reads the raw input, leaving the parsing to each
+    /// part.
+    fn parse(&self, input: &mut dyn BufRead) -> color_eyre::Result<Parsed> {
+        let mut raw = Vec::new();
+        input.read_to_end(&mut raw)?;
+        Ok(Box::new(Unparsed(raw)))
+    }
+
+    /// Solves a part using input from [`Quest::parse`]. By default this gives
+    /// the raw input to the `part_*` method for the part.
+    fn solve_parsed(
+        &self,
+        part: Part,
+        parsed: &Parsed,
+        config: &Config,
+    ) -> color_eyre::Result<Answer> {
+        let Unparsed(raw) = parsed.downcast_ref().ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "Quest {} overrides parse, so it must override solve_parsed too",
+                self.number()
+            )
+        })?;
+        let input = &mut Cursor::new(raw);
+        match part {
+            Part::One => self.part_one(input, config),
+            Part::Two => self.part_two(input, config),
+            Part::Three => self.part_three(input, config),
+        }
+    }
+
+    /// Generates a random input for a part, in the same format as the notes,
+    /// for stress testing and benchmarking. `size` scales the input, for
+    /// example the number of items or the width of a map. Returns `None` if
+    /// the quest has no generator.
+    fn generate(&self, part: Part, size: usize, rng: &mut Rng) -> Option<String> {
+        let _ = (part, size, rng);
+        None
+    }
+}
+
+/// Solves a part for the default `part_*` methods. If [`Quest::parse`] has not
+/// been overridden either, [`Quest::solve_parsed`] would only call the
+/// `part_*` method again, so that is an error instead.
+fn solve_part<Q: Quest + ?Sized>(
+    quest: &Q,
+    part: Part,
+    input: &mut dyn BufRead,
+    config: &Config,
+) -> color_eyre::Result<Answer> {
+    let parsed = quest.parse(input)?;
+    if parsed.is::<Unparsed>() {
+        return Err(color_eyre::eyre::eyre!(
+            "Part {part} of quest {} has not been implemented",
+            quest.number()
+        ));
+    }
+    quest.solve_parsed(part, &parsed, config)
+}
+
+/// Gets the value returned by [`Quest::parse`] back out of a [`Parsed`].
+pub fn downcast<T: 'static>(parsed: &Parsed) -> color_eyre::Result<&T> {
+    parsed.downcast_ref().ok_or_else(|| {
+        color_eyre::eyre::eyre!("Parsed input is not a {}", std::any::type_name::<T>())
+    })
+}
+
+/// Parsed inputs, keyed by quest and a hash of the raw input, so that parts
+/// (and examples) with identical input only parse it once.
+#[derive(Default)]
+pub struct ParseCache {
+    parsed: Mutex<HashMap<(Event, usize, u64), Arc<Parsed>>>,
+}
+
+impl ParseCache {
+    /// Parses the input for a quest, or returns the result of parsing the same
+    /// input before. The flag is true if the result came from the cache.
+    pub fn parse(
+        &self,
+        event: Event,
+        quest: &dyn Quest,
+        input: &[u8],
+    ) -> color_eyre::Result<(Arc<Parsed>, bool)> {
+        let mut hasher = DefaultHasher::new();
+        input.hash(&mut hasher);
+        let key = (event, quest.number(), hasher.finish());
+
+        if let Some(parsed) = self.parsed.lock().unwrap().get(&key) {
+            return Ok((Arc::clone(parsed), true));
+        }
+
+        // Parse without holding the lock, so other quests can parse meanwhile
+        let parsed = Arc::new(quest.parse(&mut Cursor::new(input))?);
+        self.parsed.lock().unwrap().insert(key, Arc::clone(&parsed));
+        Ok((parsed, false))
+    }
+}
+
+/// All events with solved quests, yearly events first.
+pub fn events() -> Vec<Event> {
+    let mut events: Vec<Event> = QUESTS.iter().map(|&(event, _)| event).collect();
+    events.sort();
+    events.dedup();
+    events
+}
+
+/// The most recent yearly event, which is used when no event is given.
+pub fn latest_event() -> Option<Event> {
+    events()
+        .into_iter()
+        .rfind(|event| matches!(event, Event::Year(_)))
+}
+
+/// All solved quests of an event, in order of quest number.
+pub fn all(event: Event) -> impl Iterator<Item = &'static dyn Quest> {
+    QUESTS
+        .iter()
+        .filter(move |&&(quest_event, _)| quest_event == event)
+        .map(|&(_, quest)| quest)
+}
+
+/// The quest of the event with the given number, or `None` if it has not
+/// been solved yet.
+pub fn get(event: Event, quest_number: usize) -> Option<&'static dyn Quest> {
+    all(event).find(|quest| quest.number() == quest_number)
+}
+
+/// Solves a quest of the event given by `config`.
 pub fn solve(
     quest_number: usize,
     part: Part,
-    input: impl BufRead,
+    mut input: impl BufRead,
     config: &Config,
-) -> color_eyre::Result<String> {
-    match quest_number {
-        1 => quest01::solve(part, input, config),
-        2 => quest02::solve(part, input, config),
-        3 => quest03::solve(part, input, config),
-        4 => quest04::solve(part, input, config),
-        _ => Err(color_eyre::eyre::eyre!(
+) -> color_eyre::Result<Answer> {
+    match get(config.event, quest_number) {
+        Some(quest) => quest.solve(part, &mut input, config),
+        None => Err(color_eyre::eyre::eyre!(
             "That quest has not been solved yet."
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers::Answers, common::file_reader};
+    use std::path::Path;
+
+    #[test]
+    fn quest_numbers_match_modules() {
+        assert_eq!(QUESTS.len(), MODULE_NUMBERS.len());
+        for (&(event, quest), &module_number) in QUESTS.iter().zip(MODULE_NUMBERS) {
+            assert_eq!(
+                module_number,
+                quest.number(),
+                "{event} quest{module_number:02} says it is quest {}",
+                quest.number()
+            );
+        }
+        for event in events() {
+            let numbers: Vec<usize> = all(event).map(|quest| quest.number()).collect();
+            let mut sorted = numbers.clone();
+            sorted.sort();
+            sorted.dedup();
+            assert_eq!(sorted, numbers);
+        }
+        assert!(get(Event::Year(2024), 1).is_some_and(|quest| quest.number() == 1));
+        assert_eq!(Some(Event::Year(2024)), latest_event());
+    }
+
+    #[test]
+    fn solve_unknown_quest() {
+        let input = std::io::Cursor::new("");
+        assert!(solve(0, Part::One, input, &Config::test(Event::Year(2024))).is_err());
+        let input = std::io::Cursor::new("");
+        assert!(solve(1, Part::One, input, &Config::test(Event::Year(1999))).is_err());
+    }
+
+    #[test]
+    fn parse_cache_reuses_identical_input() {
+        let cache = ParseCache::default();
+        let event = Event::Year(2024);
+        let quest = get(event, 1).unwrap();
+        let (first, cached) = cache.parse(event, quest, b"ABBAC").unwrap();
+        assert!(!cached);
+        let (second, cached) = cache.parse(event, quest, b"ABBAC").unwrap();
+        assert!(cached);
+        assert!(Arc::ptr_eq(&first, &second));
+        let (_, cached) = cache.parse(event, quest, b"AxBCDDCAxD").unwrap();
+        assert!(!cached);
+    }
+
+    #[test]
+    fn solve_parsed_matches_solve() {
+        let config = Config::test(Event::Year(2024));
+        let notes_dir = config.event.notes_dir(std::path::Path::new("notes"));
+        for quest in all(config.event) {
+            for part in [Part::One, Part::Two, Part::Three] {
+                let input =
+                    std::fs::read(part.default_input_path(&notes_dir, quest.number())).unwrap();
+                let parsed = quest.parse(&mut Cursor::new(&input)).unwrap();
+                assert_eq!(
+                    quest
+                        .solve(part, &mut Cursor::new(&input), &config)
+                        .unwrap(),
+                    quest.solve_parsed(part, &parsed, &config).unwrap(),
+                    "quest {} part {part}",
+                    quest.number()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse_needs_solve_parsed() {
+        struct ParsesOnly;
+
+        impl Quest for ParsesOnly {
+            fn number(&self) -> usize {
+                99
+            }
+
+            fn title(&self) -> &'static str {
+                "Parses Only"
+            }
+
+            fn parse(&self, _input: &mut dyn BufRead) -> color_eyre::Result<Parsed> {
+                Ok(Box::new(42))
+            }
+        }
+
+        let err = ParsesOnly
+            .solve(
+                Part::One,
+                &mut Cursor::new(""),
+                &Config::test(Event::Year(2024)),
+            )
+            .unwrap_err();
+        assert_eq!(
+            "Quest 99 overrides parse, so it must override solve_parsed too",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn unimplemented_parts_are_errors() {
+        struct PartOneOnly;
+
+        impl Quest for PartOneOnly {
+            fn number(&self) -> usize {
+                98
+            }
+
+            fn title(&self) -> &'static str {
+                "Part One Only"
+            }
+
+            fn part_one(
+                &self,
+                _input: &mut dyn BufRead,
+                _config: &Config,
+            ) -> color_eyre::Result<Answer> {
+                Ok(1.into())
+            }
+        }
+
+        let config = Config::test(Event::Year(2024));
+        let solve = |part| PartOneOnly.solve(part, &mut Cursor::new("x"), &config);
+        assert_eq!(Answer::from(1), solve(Part::One).unwrap());
+        assert_eq!(
+            "Part 2 of quest 98 has not been implemented",
+            solve(Part::Two).unwrap_err().to_string()
+        );
+        let err = PartOneOnly
+            .part_three(&mut Cursor::new("x"), &config)
+            .unwrap_err();
+        assert_eq!(
+            "Part 3 of quest 98 has not been implemented",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        let config = Config::test(Event::Year(2024));
+        for quest in all(config.event) {
+            for part in [Part::One, Part::Two, Part::Three] {
+                for (seed, size) in [(1, 1), (2, 2), (3, 10), (4, 30)] {
+                    let mut rng = Rng::new(seed);
+                    let Some(input) = quest.generate(part, size, &mut rng) else {
+                        continue;
+                    };
+                    let solution = quest.solve(part, &mut input.as_bytes(), &config);
+                    assert!(
+                        solution.is_ok(),
+                        "quest {} part {part} failed on:\n{input}\n{:?}",
+                        quest.number(),
+                        solution
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solutions_match_known_answers() {
+        for event in events() {
+            let notes_dir = event.notes_dir(Path::new("notes"));
+            let answers = Answers::load(&notes_dir).unwrap();
+            for quest in all(event) {
+                for part in [Part::One, Part::Two, Part::Three] {
+                    let Some(expected) = answers.get(quest.number(), part) else {
+                        continue;
+                    };
+                    let mut input =
+                        file_reader(part.default_input_path(&notes_dir, quest.number())).unwrap();
+                    let solution = quest.solve(part, &mut input, &Config::test(event)).unwrap();
+                    assert_eq!(
+                        *expected,
+                        solution,
+                        "{event} quest {} part {part}",
+                        quest.number()
+                    );
+                }
+            }
+        }
+    }
+}
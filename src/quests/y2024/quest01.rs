@@ -0,0 +1,217 @@
+use std::io::BufRead;
+
+use crate::{
+    common::{Answer, Config, Part, Rng},
+    quests::Quest,
+};
+
+/// Solves quest 1.
+pub struct Quest01;
+
+impl Quest for Quest01 {
+    fn number(&self) -> usize {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "The Battle for the Farmlands"
+    }
+
+    fn part_one(&self, input: &mut dyn BufRead, _config: &Config) -> color_eyre::Result<Answer> {
+        Ok(count_potions(parse_battles(input, 1)?).into())
+    }
+
+    fn part_two(&self, input: &mut dyn BufRead, _config: &Config) -> color_eyre::Result<Answer> {
+        Ok(count_potions(parse_battles(input, 2)?).into())
+    }
+
+    fn part_three(&self, input: &mut dyn BufRead, _config: &Config) -> color_eyre::Result<Answer> {
+        Ok(count_potions(parse_battles(input, 3)?).into())
+    }
+
+    /// `size` battles of enemies, with `x` for gaps from part two on.
+    fn generate(&self, part: Part, size: usize, rng: &mut Rng) -> Option<String> {
+        let (creatures, chunk_size): (&[char], usize) = match part {
+            Part::One => (&['A', 'B', 'C'], 1),
+            Part::Two => (&['A', 'B', 'C', 'D', 'x'], 2),
+            Part::Three => (&['A', 'B', 'C', 'D', 'x'], 3),
+        };
+        Some(
+            (0..size.max(1) * chunk_size)
+                .map(|_| *rng.choose(creatures))
+                .collect(),
+        )
+    }
+}
+
+fn count_potions(battles: Vec<Battle>) -> usize {
+    battles.iter().map(|battle| battle.potions_needed()).sum()
+}
+
+fn parse_battles(mut input: impl BufRead, chunk_size: usize) -> color_eyre::Result<Vec<Battle>> {
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+    Ok(bytes
+        .chunks(chunk_size)
+        .map(|chunk| {
+            Battle::new(
+                chunk
+                    .iter()
+                    .filter_map(|&byte| Enemy::try_from(byte).ok())
+                    .collect(),
+            )
+        })
+        .collect())
+}
+
+/// A group of enemies that attack together.
+pub struct Battle {
+    enemies: Vec<Enemy>,
+}
+
+impl Battle {
+    pub fn new(enemies: Vec<Enemy>) -> Self {
+        Self { enemies }
+    }
+
+    /// Potions needed for the battle, including the extra potions each enemy
+    /// needs when fighting alongside others.
+    pub fn potions_needed(&self) -> usize {
+        let base_potions_needed: usize = self
+            .enemies
+            .iter()
+            .map(|enemy| enemy.potions_needed())
+            .sum();
+
+        let num_enemies = self.enemies.len();
+        if num_enemies < 2 {
+            base_potions_needed
+        } else {
+            base_potions_needed + num_enemies * (num_enemies - 1)
+        }
+    }
+}
+
+/// A creature from the input, written as its initial.
+pub enum Enemy {
+    AncientAnt,
+    BadassBeetle,
+    CreepyCockroach,
+    DiabolicalDragonfly,
+}
+
+/// A character in the input that is not an enemy, such as `x` for no enemy.
+#[derive(Debug)]
+pub struct UnknownEnemy;
+
+impl TryFrom<u8> for Enemy {
+    type Error = UnknownEnemy;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            b'A' => Ok(Self::AncientAnt),
+            b'B' => Ok(Self::BadassBeetle),
+            b'C' => Ok(Self::CreepyCockroach),
+            b'D' => Ok(Self::DiabolicalDragonfly),
+            _ => Err(UnknownEnemy),
+        }
+    }
+}
+
+impl Enemy {
+    pub fn potions_needed(&self) -> usize {
+        match self {
+            Self::AncientAnt => 0,
+            Self::BadassBeetle => 1,
+            Self::CreepyCockroach => 3,
+            Self::DiabolicalDragonfly => 5,
+        }
+    }
+}
+
+/// Potions counted enemy by enemy, for the differential tests.
+#[cfg(test)]
+mod reference {
+    use crate::common::Answer;
+
+    /// Counts the potions for each enemy one at a time: its own potions, plus
+    /// one for each other enemy in its battle.
+    pub fn potions(input: &str, battle_size: usize) -> color_eyre::Result<Answer> {
+        let mut potions = 0;
+        for battle in input.as_bytes().chunks(battle_size) {
+            let enemies: Vec<u8> = battle
+                .iter()
+                .copied()
+                .filter(|enemy| b"ABCD".contains(enemy))
+                .collect();
+            for enemy in enemies.iter() {
+                potions += match enemy {
+                    b'A' => 0,
+                    b'B' => 1,
+                    b'C' => 3,
+                    _ => 5,
+                };
+                potions += enemies.len() - 1;
+            }
+        }
+        Ok(potions.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{file_reader, Event, Part},
+        differential::assert_matches_reference,
+        examples::assert_examples,
+    };
+
+    const CONFIG: Config = Config::test(Event::Year(2024));
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Quest01, Part::One, &CONFIG, |input| {
+            reference::potions(input, 1)
+        });
+        assert_matches_reference(&Quest01, Part::Two, &CONFIG, |input| {
+            reference::potions(input, 2)
+        });
+        assert_matches_reference(&Quest01, Part::Three, &CONFIG, |input| {
+            reference::potions(input, 3)
+        });
+    }
+
+    #[test]
+    fn solve_part_one_example() {
+        assert_examples(&Quest01, Part::One, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_one() {
+        let mut input = file_reader("notes/2024/q01p01").unwrap();
+        assert_eq!("1328", Quest01.solve(Part::One, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_two_example() {
+        assert_examples(&Quest01, Part::Two, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_two() {
+        let mut input = file_reader("notes/2024/q01p02").unwrap();
+        assert_eq!("5626", Quest01.solve(Part::Two, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_three_example() {
+        assert_examples(&Quest01, Part::Three, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_three() {
+        let mut input = file_reader("notes/2024/q01p03").unwrap();
+        assert_eq!("27565", Quest01.solve(Part::Three, &mut input, &CONFIG).unwrap());
+    }
+}
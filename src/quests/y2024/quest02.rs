@@ -0,0 +1,367 @@
+use std::{collections::HashSet, io::BufRead};
+
+use crate::{
+    common::{Answer, Config, Direction4, Grid, ParseError, Part, Rng, SectionedInput},
+    quests::{downcast, Parsed, Quest},
+};
+
+/// Solves quest 2.
+pub struct Quest02;
+
+impl Quest for Quest02 {
+    fn number(&self) -> usize {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "The Runes of Power"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> color_eyre::Result<Parsed> {
+        Ok(Box::new(parse_input(input)?))
+    }
+
+    fn solve_parsed(
+        &self,
+        part: Part,
+        parsed: &Parsed,
+        _config: &Config,
+    ) -> color_eyre::Result<Answer> {
+        let runes = downcast::<Runes>(parsed)?;
+        Ok(match part {
+            Part::One => count_words(&runes.words, &runes.inscription),
+            Part::Two => count_matching_chars(&runes.words, &runes.inscription),
+            Part::Three => {
+                let grid = runes.grid.as_ref().map_err(Clone::clone)?;
+                count_matching_chars_in_grid(&runes.words, grid)
+            }
+        }
+        .into())
+    }
+
+    /// Words followed by an inscription of about `size` letters, or a grid
+    /// `size` letters wide for part three. Letters come from a small alphabet
+    /// so that the words often appear.
+    fn generate(&self, part: Part, size: usize, rng: &mut Rng) -> Option<String> {
+        let size = size.max(1);
+        let words: Vec<String> = (0..1 + size / 10)
+            .map(|_| {
+                let len = 1 + rng.index(5);
+                random_letters(rng, len)
+            })
+            .collect();
+        let mut input = format!("WORDS:{}\n\n", words.join(","));
+
+        match part {
+            Part::One | Part::Two => {
+                // Lines of up to 40 characters, with spaces between the runes
+                let mut line = String::new();
+                for idx in 0..size {
+                    let space = idx > 0 && rng.chance(0.15);
+                    line.push(if space { ' ' } else { *rng.choose(&LETTERS) });
+                    if line.len() == 40 || idx == size - 1 {
+                        input += line.trim_end();
+                        input.push('\n');
+                        line.clear();
+                    }
+                }
+            }
+            Part::Three => {
+                for _ in 0..size.div_ceil(3) {
+                    input += &random_letters(rng, size);
+                    input.push('\n');
+                }
+            }
+        }
+        Some(input)
+    }
+}
+
+/// Letters used by generated inputs.
+const LETTERS: [char; 6] = ['A', 'B', 'C', 'D', 'E', 'F'];
+
+fn random_letters(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| *rng.choose(&LETTERS)).collect()
+}
+
+/// The words and the inscription they are found in, which for part three is a
+/// grid.
+struct Runes {
+    words: Vec<String>,
+    inscription: String,
+    /// Only part three needs the inscription to be a grid, so this only fails
+    /// that part.
+    grid: Result<Grid<char>, ParseError>,
+}
+
+fn parse_input(input: impl BufRead) -> color_eyre::Result<Runes> {
+    let input = SectionedInput::read(input)?;
+    let mut sections = input.sections();
+    let words = sections
+        .expect("'WORDS:' followed by the words")?
+        .header("WORDS", "a word")?
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let inscription = sections.expect("the inscription")?;
+    sections.finish()?;
+
+    Ok(Runes {
+        words,
+        inscription: inscription.text(),
+        grid: inscription.grid(Ok),
+    })
+}
+
+fn count_words(wordlist: &[String], inscription: &str) -> usize {
+    let mut count = 0;
+    for (idx, _) in inscription.char_indices() {
+        for word in wordlist.iter() {
+            if inscription[idx..].starts_with(word.as_str()) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn count_matching_chars(wordlist: &[String], inscription: &str) -> usize {
+    // Add reversed words to wordlist
+    let wordlist: Vec<String> = wordlist
+        .iter()
+        .flat_map(|word| [word.clone(), word.chars().rev().collect()])
+        .collect();
+
+    let mut matching_indices = HashSet::new();
+
+    for (idx_start, _) in inscription.char_indices() {
+        for word in wordlist.iter() {
+            if inscription[idx_start..].starts_with(word.as_str()) {
+                for idx in idx_start..idx_start + word.len() {
+                    matching_indices.insert(idx);
+                }
+            }
+        }
+    }
+
+    matching_indices.len()
+}
+
+fn count_matching_chars_in_grid(wordlist: &[String], grid: &Grid<char>) -> usize {
+    let height = grid.height() as i64;
+
+    let mut matching_indices = HashSet::new();
+
+    for (start, &first) in grid.cells() {
+        for direction in Direction4::ALL {
+            // Words wrap around from one side of the grid to the other, but
+            // not from the top to the bottom
+            let positions = (0..).map(|idx| start + direction.vector() * idx);
+            for word in wordlist.iter().filter(|word| word.starts_with(first)) {
+                let word_matches = positions.clone().zip(word.chars()).all(|(pos, char)| {
+                    (0..height).contains(&pos.y) && *grid.get_wrapping(pos) == char
+                });
+
+                if word_matches {
+                    for pos in positions.clone().take(word.chars().count()) {
+                        matching_indices.insert(grid.wrap(pos));
+                    }
+                }
+            }
+        }
+    }
+
+    matching_indices.len()
+}
+
+/// Rune searches that compare every word with every window of the
+/// inscription, for the differential tests.
+#[cfg(test)]
+mod reference {
+    use std::collections::HashSet;
+
+    use crate::common::Answer;
+
+    /// Splits the notes into the words from the first line and the lines of
+    /// the inscription after the blank line.
+    fn parse(input: &str) -> color_eyre::Result<(Vec<String>, Vec<&str>)> {
+        let mut lines = input.lines();
+        let words = lines
+            .next()
+            .and_then(|line| line.trim_end().strip_prefix("WORDS:"))
+            .ok_or_else(|| color_eyre::eyre::eyre!("Expected 'WORDS:'"))?;
+        let words = words.split(',').map(str::to_string).collect();
+        let inscription = lines.filter(|line| !line.trim().is_empty()).collect();
+        Ok((words, inscription))
+    }
+
+    /// The inscription as one string, with a newline after each line.
+    fn inscription_text(inscription: &[&str]) -> Vec<char> {
+        inscription
+            .iter()
+            .flat_map(|line| line.chars().chain(['\n']))
+            .collect()
+    }
+
+    fn words_and_reversed(wordlist: &[String]) -> Vec<Vec<char>> {
+        wordlist
+            .iter()
+            .flat_map(|word| [word.chars().collect(), word.chars().rev().collect()])
+            .collect()
+    }
+
+    /// Counts each word at every position in the inscription.
+    pub fn part_one(input: &str) -> color_eyre::Result<Answer> {
+        let (wordlist, inscription) = parse(input)?;
+        let inscription = inscription_text(&inscription);
+        let count: usize = wordlist
+            .iter()
+            .map(|word| {
+                let word: Vec<char> = word.chars().collect();
+                inscription
+                    .windows(word.len())
+                    .filter(|window| *window == word)
+                    .count()
+            })
+            .sum();
+        Ok(count.into())
+    }
+
+    /// Marks the characters of every match of each word, forwards and
+    /// backwards.
+    pub fn part_two(input: &str) -> color_eyre::Result<Answer> {
+        let (wordlist, inscription) = parse(input)?;
+        let inscription = inscription_text(&inscription);
+        let mut marked = vec![false; inscription.len()];
+        for word in words_and_reversed(&wordlist) {
+            for (start, window) in inscription.windows(word.len()).enumerate() {
+                if window == word {
+                    marked[start..start + word.len()].fill(true);
+                }
+            }
+        }
+        Ok(marked.iter().filter(|&&marked| marked).count().into())
+    }
+
+    /// Searches each row, repeated so that words can wrap around, and each
+    /// column for the words forwards and backwards.
+    pub fn part_three(input: &str) -> color_eyre::Result<Answer> {
+        let (wordlist, inscription) = parse(input)?;
+        let grid: Vec<Vec<char>> = inscription
+            .iter()
+            .map(|line| line.trim_end().chars().collect())
+            .collect();
+        let width = grid[0].len();
+        if grid.iter().any(|row| row.len() != width) {
+            return Err(color_eyre::eyre::eyre!("Grid is not rectangular"));
+        }
+
+        let mut marked = HashSet::new();
+        for word in words_and_reversed(&wordlist) {
+            for (y, row) in grid.iter().enumerate() {
+                let repeated: Vec<char> =
+                    row.iter().cycle().take(width + word.len()).copied().collect();
+                for x in 0..width {
+                    if repeated[x..x + word.len()] == word {
+                        marked.extend((x..x + word.len()).map(|x| (x % width, y)));
+                    }
+                }
+            }
+            for x in 0..width {
+                let column: Vec<char> = grid.iter().map(|row| row[x]).collect();
+                for (y, window) in column.windows(word.len()).enumerate() {
+                    if window == word {
+                        marked.extend((y..y + word.len()).map(|y| (x, y)));
+                    }
+                }
+            }
+        }
+        Ok(marked.len().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{file_reader, parse_error_message, Event, Part},
+        differential::assert_matches_reference,
+        examples::assert_examples,
+    };
+
+    const CONFIG: Config = Config::test(Event::Year(2024));
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Quest02, Part::One, &CONFIG, reference::part_one);
+        assert_matches_reference(&Quest02, Part::Two, &CONFIG, reference::part_two);
+        assert_matches_reference(&Quest02, Part::Three, &CONFIG, reference::part_three);
+    }
+
+    #[test]
+    fn solve_part_one_example() {
+        assert_examples(&Quest02, Part::One, &CONFIG);
+    }
+
+    #[test]
+    fn malformed_input() {
+        let error = |input: &str| {
+            parse_error_message(Quest02.solve(Part::One, &mut input.as_bytes(), &CONFIG))
+        };
+        assert_eq!("Expected 'WORDS:' at line 1, column 1", error("WRODS:A\n\nA"));
+        assert_eq!("Expected a word at line 1, column 9", error("WORDS:A,,B\n\nA"));
+        assert_eq!("Expected a blank line at line 2, column 1", error("WORDS:A\nA"));
+        assert_eq!(
+            "Expected the inscription at line 3, column 1",
+            error("WORDS:A\n\n")
+        );
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let input = "WORDS:AB,C\r\n\r\nABC\r\nCBA\r\n";
+        assert_eq!("3", Quest02.solve(Part::One, &mut input.as_bytes(), &CONFIG).unwrap());
+        assert_eq!("6", Quest02.solve(Part::Three, &mut input.as_bytes(), &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn ragged_grid_only_fails_part_three() {
+        let input = "WORDS:AB\n\nABC\nAB\n";
+        assert_eq!("2", Quest02.solve(Part::One, &mut input.as_bytes(), &CONFIG).unwrap());
+        let err = Quest02
+            .solve(Part::Three, &mut input.as_bytes(), &CONFIG)
+            .unwrap_err();
+        assert_eq!(
+            "Expected 3 cells like the first row, found 2 at line 4, column 3",
+            err.downcast::<ParseError>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn solve_part_one() {
+        let mut input = file_reader("notes/2024/q02p01").unwrap();
+        assert_eq!("33", Quest02.solve(Part::One, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_two_example() {
+        assert_examples(&Quest02, Part::Two, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_two() {
+        let mut input = file_reader("notes/2024/q02p02").unwrap();
+        assert_eq!("5280", Quest02.solve(Part::Two, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_three_example() {
+        assert_examples(&Quest02, Part::Three, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_three() {
+        let mut input = file_reader("notes/2024/q02p03").unwrap();
+        assert_eq!("11667", Quest02.solve(Part::Three, &mut input, &CONFIG).unwrap());
+    }
+}
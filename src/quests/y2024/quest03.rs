@@ -0,0 +1,433 @@
+use std::{fmt::Display, io::BufRead};
+
+use crate::{
+    common::{
+        gradient, Answer, ColourSupport, Config, Direction4, Direction8, Frame, Grid, Image, Part,
+        Rgb, Rng, Vector, Visualizer,
+    },
+    quests::{downcast, Parsed, Quest},
+};
+
+/// Solves quest 3.
+pub struct Quest03;
+
+impl Quest for Quest03 {
+    fn number(&self) -> usize {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Mining Maestro"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> color_eyre::Result<Parsed> {
+        Ok(Box::new(DepthMap::parse(input)?))
+    }
+
+    fn solve_parsed(
+        &self,
+        part: Part,
+        parsed: &Parsed,
+        config: &Config,
+    ) -> color_eyre::Result<Answer> {
+        // Digging changes the map, so each part works on its own copy
+        let mut depth_map = downcast::<DepthMap>(parsed)?.clone();
+        depth_map.set_diagonal_neighbours(part == Part::Three);
+        let mut visualizer = config.visualize.visualizer(&part.key(self.number()))?;
+        excavate(depth_map, visualizer.as_mut())
+    }
+
+    /// A `size` by `size` map of overlapping oval blobs of earth.
+    fn generate(&self, _part: Part, size: usize, rng: &mut Rng) -> Option<String> {
+        let size = size.max(1);
+        let mut map = vec![vec!['.'; size]; size];
+        for _ in 0..size.div_ceil(8) {
+            let centre = (rng.index(size) as f64, rng.index(size) as f64);
+            let radius = (
+                1.0 + rng.index(size / 4 + 1) as f64,
+                1.0 + rng.index(size / 4 + 1) as f64,
+            );
+            for (y, row) in map.iter_mut().enumerate() {
+                for (x, tile) in row.iter_mut().enumerate() {
+                    let dx = (x as f64 - centre.0) / radius.0;
+                    let dy = (y as f64 - centre.1) / radius.1;
+                    if dx * dx + dy * dy <= 1.0 {
+                        *tile = '#';
+                    }
+                }
+            }
+        }
+        Some(
+            map.into_iter()
+                .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+                .collect(),
+        )
+    }
+}
+
+fn excavate(
+    mut depth_map: DepthMap,
+    visualizer: &mut dyn Visualizer,
+) -> color_eyre::Result<Answer> {
+    log::trace!("Initial map:\n{depth_map}");
+    visualizer.frame("initial", &depth_map)?;
+    depth_map.trim();
+    log::debug!(
+        "Trimmed map to {}x{}",
+        depth_map.map.width(),
+        depth_map.map.height()
+    );
+    visualizer.frame("trimmed", &depth_map)?;
+
+    let mut rounds = 0;
+    loop {
+        log::trace!("After {rounds} round(s):\n{depth_map}");
+        let blocks_dug = depth_map.dig();
+        if blocks_dug == 0 {
+            break;
+        }
+        rounds += 1;
+        log::debug!("Round {rounds}: dug {blocks_dug} block(s)");
+        visualizer.frame(&format!("round {rounds}"), &depth_map)?;
+    }
+    visualizer.finish()?;
+
+    Ok(depth_map.sum().into())
+}
+
+/// A map of how deep each tile has been dug, where `0` is a tile that is not
+/// to be dug.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DepthMap {
+    map: Grid<u32>,
+    diagonal_neighbours: bool,
+}
+
+impl DepthMap {
+    pub fn new(map: Grid<u32>) -> Self {
+        Self {
+            map,
+            diagonal_neighbours: false,
+        }
+    }
+
+    pub fn set_diagonal_neighbours(&mut self, diagonal_neighbours: bool) {
+        self.diagonal_neighbours = diagonal_neighbours;
+    }
+
+    pub fn parse(mut input: impl BufRead) -> color_eyre::Result<Self> {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        let map = Grid::parse(&text, |c| match c {
+            '.' => Ok(0),
+            '#' => Ok(1),
+            _ => Err(format!("Expected '.' or '#', found '{c}'")),
+        })?;
+        Ok(Self::new(map))
+    }
+
+    pub fn trim(&mut self) {
+        // A completely flat map is trimmed to a single tile
+        self.map = self
+            .map
+            .trimmed(|&depth| depth > 0)
+            .unwrap_or_else(|| Grid::new(1, 1, 0));
+    }
+
+    pub fn dig(&mut self) -> usize {
+        let snapshot = self.map.clone();
+        let mut blocks_dug = 0;
+        let neighbours: Vec<Vector> = if self.diagonal_neighbours {
+            Direction8::ALL.map(Vector::from).to_vec()
+        } else {
+            Direction4::ALL.map(Vector::from).to_vec()
+        };
+
+        for (pos, &depth) in snapshot.cells() {
+            if depth == 0 {
+                continue;
+            }
+
+            // Tiles beyond the edge of the map are never dug
+            let same_neighbours = neighbours
+                .iter()
+                .all(|&offset| snapshot.get(pos + offset) == Some(&depth));
+            if same_neighbours {
+                self.map[pos] += 1;
+                blocks_dug += 1;
+            }
+        }
+        blocks_dug
+    }
+
+    pub fn sum(&self) -> usize {
+        self.map.values().map(|&d| d as usize).sum()
+    }
+
+    pub fn max_depth(&self) -> u32 {
+        self.map.values().copied().max().unwrap_or(0)
+    }
+
+    /// Draws the map with a coloured cell for each tile, which unlike the hex
+    /// digits of `Display` stays aligned at any depth, followed by a legend.
+    fn render_colour(&self, colours: ColourSupport) -> String {
+        let max_depth = self.max_depth();
+        let colour = |depth| colours.background(depth_colour(depth, max_depth));
+
+        let mut output = String::new();
+        for row in self.map.rows() {
+            let mut current = 0;
+            for &depth in row.iter() {
+                if depth != current {
+                    output += &match depth {
+                        0 => RESET.to_string(),
+                        _ => colour(depth),
+                    };
+                    current = depth;
+                }
+                output.push(if depth == 0 { '.' } else { ' ' });
+            }
+            output += RESET;
+            output.push('\n');
+        }
+
+        output += "depth:";
+        for depth in legend_depths(max_depth) {
+            output += &format!(" {}  {RESET} {depth}", colour(depth));
+        }
+        output
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+/// Colour of tiles that have not been dug, in images.
+const GROUND: Rgb = [40, 40, 40];
+
+/// Colour of a dug tile, from dark for shallow to bright for the deepest.
+fn depth_colour(depth: u32, max_depth: u32) -> Rgb {
+    gradient((depth.max(1) - 1) as f64 / (max_depth.max(2) - 1) as f64)
+}
+
+/// Up to ten depths from 1 to the maximum depth, evenly spread, to show in the
+/// legend.
+fn legend_depths(max_depth: u32) -> Vec<u32> {
+    const STEPS: usize = 10;
+    if (max_depth as usize) <= STEPS {
+        return (1..=max_depth).collect();
+    }
+    (0..STEPS)
+        .map(|step| 1 + ((max_depth - 1) as usize * step / (STEPS - 1)) as u32)
+        .collect()
+}
+
+impl Display for DepthMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in self.map.rows() {
+            for &depth in row {
+                if depth == 0 {
+                    write!(f, ".")?;
+                } else {
+                    write!(f, "{:X}", depth)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Frame for DepthMap {
+    fn text(&self) -> String {
+        self.to_string()
+    }
+
+    fn ansi(&self) -> String {
+        self.render_colour(ColourSupport::detect())
+    }
+
+    fn image(&self) -> Option<Image> {
+        let max_depth = self.max_depth();
+        Some(Image::from_grid(&self.map, |&depth| match depth {
+            0 => GROUND,
+            _ => depth_colour(depth, max_depth),
+        }))
+    }
+}
+
+/// Digging with every tile's depth in a hash map, checking every tile in
+/// every round, to compare the solution with.
+#[cfg(test)]
+mod reference {
+    use std::collections::HashMap;
+
+    use crate::common::Answer;
+
+    /// Digs round by round, without trimming the map first: in round `n`, each
+    /// tile at depth `n` whose neighbours are all at depth `n` too gets one
+    /// deeper. Tiles outside the map, like `.` tiles, are at depth 0.
+    pub fn dig(input: &str, diagonal_neighbours: bool) -> color_eyre::Result<Answer> {
+        let mut depths: HashMap<(i64, i64), u64> = HashMap::new();
+        let mut width = None;
+        for (y, line) in input.lines().enumerate() {
+            let line = line.trim_ascii_end();
+            if *width.get_or_insert(line.len()) != line.len() {
+                return Err(color_eyre::eyre::eyre!("Map is not rectangular"));
+            }
+            for (x, tile) in line.chars().enumerate() {
+                match tile {
+                    '#' => {
+                        depths.insert((x as i64, y as i64), 1);
+                    }
+                    '.' => {}
+                    _ => return Err(color_eyre::eyre::eyre!("Unknown tile '{tile}'")),
+                }
+            }
+        }
+
+        let mut neighbours = vec![(0, -1), (1, 0), (0, 1), (-1, 0)];
+        if diagonal_neighbours {
+            neighbours.extend([(1, -1), (1, 1), (-1, 1), (-1, -1)]);
+        }
+
+        for round in 1.. {
+            let deeper: Vec<(i64, i64)> = depths
+                .iter()
+                .filter(|&(&(x, y), &depth)| {
+                    depth == round
+                        && neighbours
+                            .iter()
+                            .all(|(dx, dy)| depths.get(&(x + dx, y + dy)) == Some(&round))
+                })
+                .map(|(&tile, _)| tile)
+                .collect();
+            if deeper.is_empty() {
+                break;
+            }
+            for tile in deeper {
+                *depths.get_mut(&tile).unwrap() += 1;
+            }
+        }
+
+        Ok(depths.values().sum::<u64>().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{file_reader, parse_error_message, Event, Part},
+        differential::assert_matches_reference,
+        examples::assert_examples,
+    };
+
+    const CONFIG: Config = Config::test(Event::Year(2024));
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Quest03, Part::One, &CONFIG, |input| {
+            reference::dig(input, false)
+        });
+        assert_matches_reference(&Quest03, Part::Two, &CONFIG, |input| {
+            reference::dig(input, false)
+        });
+        assert_matches_reference(&Quest03, Part::Three, &CONFIG, |input| {
+            reference::dig(input, true)
+        });
+    }
+
+    #[test]
+    fn deep_maps_do_not_overflow() {
+        // Start from tiles already dug 255 deep rather than digging a map
+        // hundreds of tiles across, which is slow without optimizations
+        let mut map = DepthMap::new(Grid::new(5, 5, 255));
+        assert_eq!(9, map.dig());
+        assert_eq!(1, map.dig());
+        assert_eq!(0, map.dig());
+        assert_eq!(25 * 255 + 9 + 1, map.sum());
+        assert_eq!(257, map.max_depth());
+    }
+
+    #[test]
+    fn legend_depths_spread() {
+        assert_eq!(Vec::<u32>::new(), legend_depths(0));
+        assert_eq!(vec![1, 2, 3], legend_depths(3));
+        assert_eq!(vec![1, 4, 7, 10, 13, 16, 19, 22, 25, 28], legend_depths(28));
+    }
+
+    #[test]
+    fn colour_rendering_stays_aligned() {
+        let map = DepthMap::new(Grid::from_rows(vec![vec![0, 1, 6, 0], vec![1, 7, 7, 1]]).unwrap());
+        let rendered = map.render_colour(ColourSupport::TrueColour);
+        let rows: Vec<String> = rendered.lines().map(strip_escapes).collect();
+        assert_eq!(vec![".  .", "    ", "depth:    1    2    3    4    5    6    7"], rows);
+        assert!(rendered.starts_with(".\x1b[48;2;68;1;84m \x1b[48;2;"));
+    }
+
+    fn strip_escapes(line: &str) -> String {
+        let mut output = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                output.push(c);
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn malformed_input() {
+        let error = |input: &str| parse_error_message(DepthMap::parse(input.as_bytes()));
+        assert_eq!(
+            "Expected '.' or '#', found 'x' at line 2, column 2",
+            error("..#\n.x.")
+        );
+        assert_eq!(
+            "Expected 3 cells like the first row, found 2 at line 2, column 3",
+            error("..#\n.#")
+        );
+    }
+
+    #[test]
+    fn depth_map_trim() {
+        let mut map = DepthMap::parse(".....\n.....\n.##..\n...#.\n...#.\n.##..\n.....".as_bytes())
+            .unwrap();
+
+        map.trim();
+
+        let expected = DepthMap::parse("##.\n..#\n..#\n##.".as_bytes()).unwrap();
+
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    fn solve_part_one_example() {
+        assert_examples(&Quest03, Part::One, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_one() {
+        let mut input = file_reader("notes/2024/q03p01").unwrap();
+        assert_eq!("120", Quest03.solve(Part::One, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_two() {
+        let mut input = file_reader("notes/2024/q03p02").unwrap();
+        assert_eq!("2712", Quest03.solve(Part::Two, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_three_example() {
+        assert_examples(&Quest03, Part::Three, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_three() {
+        let mut input = file_reader("notes/2024/q03p03").unwrap();
+        assert_eq!("10336", Quest03.solve(Part::Three, &mut input, &CONFIG).unwrap());
+    }
+}
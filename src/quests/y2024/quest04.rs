@@ -0,0 +1,171 @@
+use std::io::BufRead;
+
+use crate::{
+    common::{Answer, Config, Part, Rng, SectionedInput},
+    quests::{downcast, Parsed, Quest},
+};
+
+/// Solves quest 4.
+pub struct Quest04;
+
+impl Quest for Quest04 {
+    fn number(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Royal Smith's Puzzle"
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> color_eyre::Result<Parsed> {
+        Ok(Box::new(parse_nails(input)?))
+    }
+
+    fn solve_parsed(
+        &self,
+        part: Part,
+        parsed: &Parsed,
+        _config: &Config,
+    ) -> color_eyre::Result<Answer> {
+        let nails = downcast::<Vec<i64>>(parsed)?;
+        Ok(match part {
+            Part::One | Part::Two => count_strikes(nails),
+            Part::Three => count_bidirectional_strikes(nails),
+        }
+        .into())
+    }
+
+    /// `size` nails, which are longer in later parts like in the notes.
+    fn generate(&self, part: Part, size: usize, rng: &mut Rng) -> Option<String> {
+        let max_length = match part {
+            Part::One => 20,
+            Part::Two => 10_000,
+            Part::Three => 10_000_000,
+        };
+        Some(
+            (0..size.max(1))
+                .map(|_| format!("{}\n", rng.range(1..=max_length)))
+                .collect(),
+        )
+    }
+}
+
+/// Reads the length of each nail, one per line.
+fn parse_nails(input: impl BufRead) -> color_eyre::Result<Vec<i64>> {
+    let input = SectionedInput::read(input)?;
+    let mut sections = input.sections();
+    let nails = sections
+        .expect("the lengths of the nails")?
+        .numbers("the length of a nail")?;
+    sections.finish()?;
+    Ok(nails)
+}
+
+/// Strikes needed to hammer every nail down to the shortest one.
+fn count_strikes(nails: &[i64]) -> i64 {
+    let minimum = nails.iter().copied().min().unwrap_or(0);
+    nails.iter().map(|nail| nail - minimum).sum()
+}
+
+/// Strikes needed to level the nails when they can be pulled up as well as
+/// hammered down, which is least when they all meet at the median.
+fn count_bidirectional_strikes(nails: &[i64]) -> i64 {
+    let mut nails = nails.to_vec();
+    nails.sort();
+    let median = nails[nails.len() / 2];
+
+    nails.iter().map(|nail| (nail - median).abs()).sum()
+}
+
+/// Strikes counted for every nail length that could be the best one, to
+/// compare the solution with.
+#[cfg(test)]
+mod reference {
+    use crate::common::Answer;
+
+    /// Tries levelling the nails to the length of each nail in turn, and keeps
+    /// the fewest strikes. Without pulling, nails can only be hammered down, so
+    /// only lengths no longer than any nail are possible. The best length is
+    /// always one of the nails' lengths, so there is no need to try others.
+    pub fn strikes(input: &str, pull: bool) -> color_eyre::Result<Answer> {
+        let nails = input
+            .lines()
+            .map(|line| line.trim_ascii_end().parse())
+            .collect::<Result<Vec<i64>, _>>()?;
+        nails
+            .iter()
+            .filter(|&&target| pull || nails.iter().all(|&nail| nail >= target))
+            .map(|&target| nails.iter().map(|&nail| (nail - target).abs()).sum::<i64>())
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| color_eyre::eyre::eyre!("No nails"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{file_reader, parse_error_message, Event, Part},
+        differential::assert_matches_reference,
+        examples::assert_examples,
+    };
+
+    const CONFIG: Config = Config::test(Event::Year(2024));
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Quest04, Part::One, &CONFIG, |input| {
+            reference::strikes(input, false)
+        });
+        assert_matches_reference(&Quest04, Part::Two, &CONFIG, |input| {
+            reference::strikes(input, false)
+        });
+        assert_matches_reference(&Quest04, Part::Three, &CONFIG, |input| {
+            reference::strikes(input, true)
+        });
+    }
+
+    #[test]
+    fn solve_part_one_example() {
+        assert_examples(&Quest04, Part::One, &CONFIG);
+    }
+
+    #[test]
+    fn malformed_input() {
+        let error = |input: &str| {
+            parse_error_message(Quest04.solve(Part::Three, &mut input.as_bytes(), &CONFIG))
+        };
+        assert_eq!(
+            "Expected the length of a nail: invalid digit found in string at line 2, column 1",
+            error("3\n4x\n")
+        );
+        assert_eq!(
+            "Expected the lengths of the nails at line 1, column 1",
+            error("")
+        );
+    }
+
+    #[test]
+    fn solve_part_one() {
+        let mut input = file_reader("notes/2024/q04p01").unwrap();
+        assert_eq!("80", Quest04.solve(Part::One, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_two() {
+        let mut input = file_reader("notes/2024/q04p02").unwrap();
+        assert_eq!("824608", Quest04.solve(Part::Two, &mut input, &CONFIG).unwrap());
+    }
+
+    #[test]
+    fn solve_part_three_example() {
+        assert_examples(&Quest04, Part::Three, &CONFIG);
+    }
+
+    #[test]
+    fn solve_part_three() {
+        let mut input = file_reader("notes/2024/q04p03").unwrap();
+        assert_eq!("122004276", Quest04.solve(Part::Three, &mut input, &CONFIG).unwrap());
+    }
+}